@@ -1,4 +1,4 @@
-use salt::{salt_app, Dimensions, EventType, MouseEvent};
+use salt::{salt_app, Dimensions, Event, EventType};
 use wasm_bindgen::prelude::*;
 
 pub struct CounterApp {
@@ -16,16 +16,20 @@ impl AppCore for CounterApp {
         }
     }
 
-    fn handle_event(&mut self, event: MouseEvent) -> bool {
+    fn handle_event(&mut self, event: Event) -> bool {
+        let Event::Mouse(event) = event else {
+            return false;
+        };
+
         match event.event_type {
-            EventType::MouseDown => {
+            EventType::MouseDown | EventType::PointerDown => {
                 // Check if the click is within the button area
                 if is_point_in_rect(event.x as f32, event.y as f32, 250.0, 150.0, 300.0, 100.0) {
                     self.btn_clicked = true;
                     return true;
                 }
             }
-            EventType::MouseUp => {
+            EventType::MouseUp | EventType::PointerUp => {
                 if self.btn_clicked {
                     // Only increment counter if mouse up is also over button (complete click)
                     if is_point_in_rect(event.x as f32, event.y as f32, 250.0, 150.0, 300.0, 100.0)
@@ -36,7 +40,7 @@ impl AppCore for CounterApp {
                     return true;
                 }
             }
-            EventType::MouseMove => {
+            EventType::MouseMove | EventType::PointerMove => {
                 // Check for hover state
                 let hover_now =
                     is_point_in_rect(event.x as f32, event.y as f32, 250.0, 150.0, 300.0, 100.0);
@@ -210,6 +210,10 @@ fn canvas(area: Area, app: &mut DrawingApp) {
                         }
                         state.is_drawing = false;
                     }
+                    DragPhase::Cancel => {
+                        state.strokes.pop();
+                        state.is_drawing = false;
+                    }
                 },
             ),
     );
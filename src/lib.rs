@@ -3,21 +3,44 @@
 //! Salt provides a simple interface for creating SVG-based web applications
 //! using Rust, with WebAssembly as the compilation target.
 
+pub mod persist;
+pub mod recording;
+pub mod testing;
 pub mod ui;
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::rc::Rc;
 
-pub use crate::ui::{Color, DragPhase, Point, TextAlign};
+pub use crate::ui::{
+    Color, Cursor, DragPhase, EventCtx, GestureInfo, Point, Store, TextAlign, Update,
+};
+pub use js_sys;
 use ui::AppCtx;
 pub use wasm_bindgen;
 pub use web_sys;
 
 /// Event types that can be handled by Salt applications
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum EventType {
     Click,
     MouseDown,
     MouseUp,
     MouseMove,
+    KeyDown,
+    KeyUp,
+    Wheel,
+    PointerDown,
+    PointerUp,
+    PointerMove,
+    PointerCancel,
+    PointerLeave,
+    Input,
+    Paste,
+    Cut,
+    CompositionStart,
+    CompositionUpdate,
+    CompositionEnd,
 }
 
 impl fmt::Display for EventType {
@@ -27,24 +50,143 @@ impl fmt::Display for EventType {
             EventType::MouseDown => write!(f, "mousedown"),
             EventType::MouseUp => write!(f, "mouseup"),
             EventType::MouseMove => write!(f, "mousemove"),
+            EventType::KeyDown => write!(f, "keydown"),
+            EventType::KeyUp => write!(f, "keyup"),
+            EventType::Wheel => write!(f, "wheel"),
+            EventType::PointerDown => write!(f, "pointerdown"),
+            EventType::PointerUp => write!(f, "pointerup"),
+            EventType::PointerMove => write!(f, "pointermove"),
+            EventType::PointerCancel => write!(f, "pointercancel"),
+            EventType::PointerLeave => write!(f, "pointerleave"),
+            EventType::Input => write!(f, "input"),
+            EventType::Paste => write!(f, "paste"),
+            EventType::Cut => write!(f, "cut"),
+            EventType::CompositionStart => write!(f, "compositionstart"),
+            EventType::CompositionUpdate => write!(f, "compositionupdate"),
+            EventType::CompositionEnd => write!(f, "compositionend"),
         }
     }
 }
 
-impl From<&str> for EventType {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for EventType {
+    type Error = ParseEventTypeError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
         match s {
-            "click" => EventType::Click,
-            "mousedown" => EventType::MouseDown,
-            "mouseup" => EventType::MouseUp,
-            "mousemove" => EventType::MouseMove,
-            _ => EventType::Click, // Default to Click for unknown events
+            "click" => Ok(EventType::Click),
+            "mousedown" => Ok(EventType::MouseDown),
+            "mouseup" => Ok(EventType::MouseUp),
+            "mousemove" => Ok(EventType::MouseMove),
+            "keydown" => Ok(EventType::KeyDown),
+            "keyup" => Ok(EventType::KeyUp),
+            "wheel" => Ok(EventType::Wheel),
+            "pointerdown" => Ok(EventType::PointerDown),
+            "pointerup" => Ok(EventType::PointerUp),
+            "pointermove" => Ok(EventType::PointerMove),
+            "pointercancel" => Ok(EventType::PointerCancel),
+            "pointerleave" => Ok(EventType::PointerLeave),
+            "input" => Ok(EventType::Input),
+            "paste" => Ok(EventType::Paste),
+            "cut" => Ok(EventType::Cut),
+            "compositionstart" => Ok(EventType::CompositionStart),
+            "compositionupdate" => Ok(EventType::CompositionUpdate),
+            "compositionend" => Ok(EventType::CompositionEnd),
+            _ => Err(ParseEventTypeError {
+                event_type: s.to_string(),
+                expected: None,
+            }),
+        }
+    }
+}
+
+impl EventType {
+    /// Whether this is a mouse or pointer event, other than a wheel event
+    pub fn is_pointer(&self) -> bool {
+        matches!(
+            self,
+            EventType::Click
+                | EventType::MouseDown
+                | EventType::MouseUp
+                | EventType::MouseMove
+                | EventType::PointerDown
+                | EventType::PointerUp
+                | EventType::PointerMove
+                | EventType::PointerCancel
+                | EventType::PointerLeave
+        )
+    }
+
+    /// Whether this is a keyboard event
+    pub fn is_key(&self) -> bool {
+        matches!(self, EventType::KeyDown | EventType::KeyUp)
+    }
+
+    /// Whether this is a clipboard, input or composition event
+    pub fn is_text(&self) -> bool {
+        matches!(
+            self,
+            EventType::Input
+                | EventType::Paste
+                | EventType::Cut
+                | EventType::CompositionStart
+                | EventType::CompositionUpdate
+                | EventType::CompositionEnd
+        )
+    }
+
+    /// Parse a mouse or pointer event type, rejecting types of other kinds
+    pub fn parse_pointer(s: &str) -> Result<Self, ParseEventTypeError> {
+        Self::parse_kind(s, "mouse or pointer", Self::is_pointer)
+    }
+
+    /// Parse a keyboard event type, rejecting types of other kinds
+    pub fn parse_key(s: &str) -> Result<Self, ParseEventTypeError> {
+        Self::parse_kind(s, "keyboard", Self::is_key)
+    }
+
+    /// Parse a clipboard, input or composition event type, rejecting types of other kinds
+    pub fn parse_text(s: &str) -> Result<Self, ParseEventTypeError> {
+        Self::parse_kind(s, "text", Self::is_text)
+    }
+
+    fn parse_kind(
+        s: &str,
+        kind: &'static str,
+        belongs: fn(&Self) -> bool,
+    ) -> Result<Self, ParseEventTypeError> {
+        let event_type = Self::try_from(s)?;
+        if !belongs(&event_type) {
+            return Err(ParseEventTypeError {
+                event_type: s.to_string(),
+                expected: Some(kind),
+            });
+        }
+        Ok(event_type)
+    }
+}
+
+/// Error returned when a host passes an event type Salt does not know
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEventTypeError {
+    /// The rejected event type
+    pub event_type: String,
+    /// The kind of event that was wanted, when the type is known but of another kind
+    pub expected: Option<&'static str>,
+}
+
+impl fmt::Display for ParseEventTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.expected {
+            Some(kind) => write!(f, "`{}` is not a {} event", self.event_type, kind),
+            None => write!(f, "unknown event type `{}`", self.event_type),
         }
     }
 }
 
+impl std::error::Error for ParseEventTypeError {}
+
 /// Mouse event data
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct MouseEvent {
     /// Type of mouse event
     pub event_type: EventType,
@@ -52,10 +194,180 @@ pub struct MouseEvent {
     pub x: f64,
     /// Y coordinate relative to the application container
     pub y: f64,
+    /// Horizontal scroll amount for wheel events
+    pub delta_x: f64,
+    /// Vertical scroll amount for wheel events
+    pub delta_y: f64,
+    /// Depth scroll amount for wheel events
+    pub delta_z: f64,
+    /// Unit of the wheel deltas
+    pub delta_mode: DeltaMode,
+    /// Identifier of the pointer that produced the event
+    pub pointer_id: i32,
+    /// Kind of device that produced the event
+    pub pointer_type: PointerType,
+    /// Normalized pressure of the pointer in the range 0 to 1
+    pub pressure: f32,
+    /// Button that changed state, following the DOM `button` numbering
+    pub button: i16,
+    /// Bitmask of buttons currently pressed, following the DOM `buttons` numbering
+    pub buttons: u16,
+    /// Modifier keys held during the event
+    pub modifiers: Modifiers,
+    /// Time the event happened, in milliseconds
+    pub timestamp: f64,
+}
+
+impl MouseEvent {
+    /// Create a mouse event at the given position with no wheel deltas
+    pub fn new(event_type: EventType, x: f64, y: f64) -> Self {
+        Self {
+            event_type,
+            x,
+            y,
+            delta_x: 0.0,
+            delta_y: 0.0,
+            delta_z: 0.0,
+            delta_mode: DeltaMode::Pixel,
+            pointer_id: 1,
+            pointer_type: PointerType::Mouse,
+            pressure: 0.0,
+            button: 0,
+            buttons: 0,
+            modifiers: Modifiers::default(),
+            timestamp: 0.0,
+        }
+    }
+}
+
+/// Kind of device behind a pointer event
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PointerType {
+    #[default]
+    Mouse,
+    Pen,
+    Touch,
+}
+
+impl From<&str> for PointerType {
+    fn from(s: &str) -> Self {
+        match s {
+            "pen" => PointerType::Pen,
+            "touch" => PointerType::Touch,
+            _ => PointerType::Mouse,
+        }
+    }
+}
+
+/// Unit in which wheel deltas are expressed, matching the DOM `deltaMode` values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeltaMode {
+    /// Deltas are in pixels
+    #[default]
+    Pixel,
+    /// Deltas are in lines
+    Line,
+    /// Deltas are in pages
+    Page,
+}
+
+impl From<u32> for DeltaMode {
+    fn from(mode: u32) -> Self {
+        match mode {
+            1 => DeltaMode::Line,
+            2 => DeltaMode::Page,
+            _ => DeltaMode::Pixel,
+        }
+    }
+}
+
+/// Modifier keys held while an event was dispatched
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+/// Keyboard event data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyEvent {
+    /// Type of keyboard event
+    pub event_type: EventType,
+    /// Logical key value, e.g. `"a"`, `"Enter"` or `"ArrowLeft"`
+    pub key: String,
+    /// Physical key code, e.g. `"KeyA"` or `"Space"`
+    pub code: String,
+    /// Whether the key is being held down and auto-repeating
+    pub repeat: bool,
+    /// Modifier keys held during the event
+    pub modifiers: Modifiers,
+    /// Time the event happened, in milliseconds
+    pub timestamp: f64,
+}
+
+/// Text input, clipboard or input method event for the focused text field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextEvent {
+    /// One of `Input`, `Paste`, `Cut` or the `Composition*` types
+    pub event_type: EventType,
+    /// Inserted, pasted or composed text; empty for cuts
+    pub data: String,
+}
+
+/// Application-specific event forwarded on purpose by the host page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomEvent {
+    /// Name chosen by the application, e.g. `"file-loaded"`
+    pub name: String,
+    /// Payload of the event, typically JSON
+    pub detail: String,
+}
+
+/// An input event delivered to a Salt application
+#[derive(Debug, Clone)]
+pub enum Event {
+    Mouse(MouseEvent),
+    Key(KeyEvent),
+    /// The window lost focus
+    Blur,
+    /// Keyboard focus moved from the app to another part of the page
+    FocusOut,
+    /// An event sent by the host page rather than the browser
+    Custom(CustomEvent),
+    /// Text typed through an input method, pasted or cut in the focused text field
+    Text(TextEvent),
+}
+
+impl From<MouseEvent> for Event {
+    fn from(event: MouseEvent) -> Self {
+        Event::Mouse(event)
+    }
+}
+
+impl From<KeyEvent> for Event {
+    fn from(event: KeyEvent) -> Self {
+        Event::Key(event)
+    }
+}
+
+impl From<CustomEvent> for Event {
+    fn from(event: CustomEvent) -> Self {
+        Event::Custom(event)
+    }
+}
+
+impl From<TextEvent> for Event {
+    fn from(event: TextEvent) -> Self {
+        Event::Text(event)
+    }
 }
 
 /// Dimensions of the rendering surface
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Dimensions {
     /// Width in pixels
     pub width: u32,
@@ -71,14 +383,81 @@ pub trait AppCore {
     /// Create a new instance of the application
     fn new() -> Self;
 
-    /// Handle a mouse event
+    /// Handle an input event
     ///
     /// Return true if the application state changed and a re-render is needed.
-    fn handle_event(&mut self, event: MouseEvent) -> bool;
+    fn handle_event(&mut self, event: Event) -> bool;
 
     /// Render the application to SVG
     fn render(&mut self, dimensions: Dimensions) -> String;
 
+    /// Advance time-based behavior such as long presses to `now`, in milliseconds
+    ///
+    /// Return true if the application state changed and a re-render is needed.
+    fn poll(&mut self, _now: f64) -> bool {
+        false
+    }
+
+    /// The time, in milliseconds, at which `poll` should next be called
+    fn next_deadline(&mut self) -> Option<f64> {
+        None
+    }
+
+    /// Advance animations by `dt` milliseconds on an animation frame
+    ///
+    /// Return true if the application state changed and a re-render is needed.
+    fn tick(&mut self, _dt: f64) -> bool {
+        false
+    }
+
+    /// Whether the application wants the host to keep delivering animation frames
+    fn wants_frame(&mut self) -> bool {
+        false
+    }
+
+    /// Whether a shape holds pointer capture, so the host should capture the pointer too
+    fn pointer_captured(&mut self) -> bool {
+        false
+    }
+
+    /// The cursor the host should show over the app
+    fn cursor(&mut self) -> Cursor {
+        Cursor::Default
+    }
+
+    /// The text selected in the focused text field, which the host copies to the clipboard
+    fn selected_text(&mut self) -> Option<String> {
+        None
+    }
+
+    /// Bounds of the caret while a text field is focused, as `(min_x, min_y, max_x, max_y)`
+    ///
+    /// The host keeps its hidden input element here, so input methods open next to the caret.
+    fn caret_bounds(&mut self) -> Option<(f32, f32, f32, f32)> {
+        None
+    }
+
+    /// Whether handling the last event asked the host to suppress the browser's default action
+    fn default_prevented(&mut self) -> bool {
+        false
+    }
+
+    /// Set the callback the app uses to ask the host for a `poll` outside of any deadline
+    ///
+    /// Called when an async task finishes and its result is waiting to be applied.
+    fn set_waker(&mut self, _waker: Rc<dyn Fn()>) {}
+
+    /// Save any unsaved state right away, e.g. before the page is hidden
+    fn flush(&mut self) {}
+
+    /// Start recording everything the host feeds into the app
+    fn start_recording(&mut self) {}
+
+    /// Stop recording and return the session, if one was being recorded
+    fn stop_recording(&mut self) -> Option<recording::Recording> {
+        None
+    }
+
     /// Initialize the app with any setup required
     fn init(&mut self) {}
 }
@@ -100,6 +479,19 @@ pub trait App {
 
     /// Get a mutable reference to the application state
     fn state(&mut self) -> (&mut AppCtx<Self::State>, &mut Self::State);
+
+    /// Advance animations by `dt` milliseconds
+    ///
+    /// Called on animation frames requested through `AppCtx::request_animation_frame`
+    /// or `AppCtx::set_continuous_animation`.
+    fn tick(&mut self, _dt: f64) {}
+
+    /// Handle an application-specific event sent by the host page
+    ///
+    /// Return true if the application state changed and a re-render is needed.
+    fn custom_event(&mut self, _event: &CustomEvent) -> bool {
+        false
+    }
 }
 
 impl<T: App> AppCore for T {
@@ -107,147 +499,953 @@ impl<T: App> AppCore for T {
         <T as App>::new()
     }
 
-    fn handle_event(&mut self, event: MouseEvent) -> bool {
-        let x = event.x as f32;
-        let y = event.y as f32;
+    fn handle_event(&mut self, event: Event) -> bool {
+        let ctx = self.state().0;
+        ctx.default_prevented = false;
+        if ctx.recording.is_some() {
+            ctx.record(event.clone());
+        }
+
+        // Everything recorded in history while handling one event is one undo step
+        ctx.history.begin_group();
+        let changed = match event {
+            Event::Mouse(event) => handle_mouse_event(self, event),
+            Event::Key(event) => handle_key_event(self, event),
+            Event::Blur => {
+                let (ctx, state) = self.state();
+                let cancelled = cancel_interactions(ctx, state);
+                if let Some(persistence) = &mut ctx.persistence {
+                    persistence.flush(state);
+                }
+                ctx.gestures.hover.dismiss_tooltip() || cancelled
+            }
+            Event::FocusOut => {
+                let ctx = self.state().0;
+                let had_focus = ctx.focus.focused_shape_id.is_some();
+                ctx.focus = FocusState::default();
+                ctx.text_edit = None;
+                had_focus
+            }
+            Event::Custom(event) => self.custom_event(&event),
+            Event::Text(event) => {
+                let (ctx, state) = self.state();
+                edit_text_input(ctx, state, |edit, value, multiline| {
+                    edit.handle_text(value, multiline, &event)
+                })
+            }
+        };
+        let ctx = self.state().0;
+        end_history_step(ctx);
+        if changed {
+            ctx.state_changed();
+        }
+        changed
+    }
+
+    fn render(&mut self, dimensions: Dimensions) -> String {
+        let ctx = self.state().0;
+        ctx.record(recording::RecordedInput::Render(dimensions));
+        ctx.set_dimensions(dimensions);
+        let previous = std::mem::take(&mut ctx.view.shapes);
+        self.view(dimensions);
+
+        // Drags whose shape is gone from the new view are cancelled through the
+        // callbacks of the shapes they started on
         let (ctx, state) = self.state();
-        let view = &mut ctx.view;
+        let vanished: Vec<i32> = ctx
+            .gestures
+            .drags
+            .iter()
+            .filter(|(_, drag)| {
+                drag.dragging_shape_id
+                    .is_some_and(|id| ctx.view.find_shape_by_id(id).is_none())
+            })
+            .map(|(&pointer_id, _)| pointer_id)
+            .collect();
+        let drags = vanished
+            .into_iter()
+            .filter_map(|pointer_id| ctx.gestures.drags.remove_entry(&pointer_id))
+            .collect();
+        ctx.history.begin_group();
+        let cancelled = cancel_drags(&previous, state, drags);
+        end_history_step(ctx);
+        if cancelled {
+            // The cancel handlers may have changed state, so build the view again
+            ctx.state_changed();
+            ctx.clear();
+            self.view(dimensions);
+        }
 
-        // Handle mouse down event
-        if event.event_type == EventType::MouseDown {
-            // Hit test the view to check if any interactive elements were clicked
-            if let Some((idx, id)) = view.hit_test_with_id(x, y) {
-                let mut shapes = Vec::new();
-                std::mem::swap(&mut shapes, &mut view.shapes);
+        // Focus survives re-renders through stable IDs, but not the shape disappearing
+        let ctx = self.state().0;
+        if let Some(id) = ctx.focus.focused_shape_id {
+            if ctx.view.find_shape_by_id(id).is_none() {
+                ctx.focus = FocusState::default();
+            }
+        }
 
-                // Store drag start position and the element that received mouse down
-                ctx.gestures.drag.start_x = Some(x);
-                ctx.gestures.drag.start_y = Some(y);
-                ctx.gestures.drag.dragging_shape_id = Some(id);
-                ctx.gestures.drag.mouse_down_id = Some(id);
+        ctx.to_svg().to_compact_string()
+    }
 
-                // Call the on_drag handler with start phase
-                if let (Some(start_x), Some(start_y)) =
-                    (ctx.gestures.drag.start_x, ctx.gestures.drag.start_y)
-                {
-                    shapes[idx].run_on_drag(
-                        state,
-                        ui::gesture::DragPhase::Start,
-                        ui::gesture::Point::new(start_x, start_y),
-                        ui::gesture::Point::new(x, y),
-                    );
+    fn poll(&mut self, now: f64) -> bool {
+        let ctx = self.state().0;
+        ctx.record(recording::RecordedInput::Poll { now });
+        ctx.history.begin_group();
+        let changed = poll_gestures(self, now);
+        let (ctx, state) = self.state();
+        let timers = ctx.scheduler.run_due(state, now);
+        let completed = ctx.tasks.run_completed(state);
+        end_history_step(ctx);
+
+        let changed = completed || timers || changed;
+        if changed {
+            ctx.state_changed();
+        }
+        if let Some(persistence) = &mut ctx.persistence {
+            persistence.poll(state, now);
+        }
+        // Showing a tooltip only needs a render, the state is untouched
+        show_due_tooltip(ctx, now) || changed
+    }
+
+    fn next_deadline(&mut self) -> Option<f64> {
+        self.state().0.next_deadline()
+    }
+
+    fn default_prevented(&mut self) -> bool {
+        self.state().0.default_prevented
+    }
+
+    fn set_waker(&mut self, waker: Rc<dyn Fn()>) {
+        self.state().0.tasks.set_waker(waker);
+    }
+
+    fn flush(&mut self) {
+        let (ctx, state) = self.state();
+        if let Some(persistence) = &mut ctx.persistence {
+            persistence.flush(state);
+        }
+    }
+
+    fn start_recording(&mut self) {
+        self.state().0.start_recording();
+    }
+
+    fn stop_recording(&mut self) -> Option<recording::Recording> {
+        self.state().0.stop_recording()
+    }
+
+    fn tick(&mut self, dt: f64) -> bool {
+        let ctx = self.state().0;
+        ctx.record(recording::RecordedInput::Tick { dt });
+        if !ctx.frames.take_frame() {
+            return false;
+        }
+
+        // Everything an animation frame changes is one undo step, like a poll
+        ctx.history.begin_group();
+        <T as App>::tick(self, dt);
+        let ctx = self.state().0;
+        end_history_step(ctx);
+        ctx.state_changed();
+        true
+    }
+
+    fn wants_frame(&mut self) -> bool {
+        self.state().0.frames.wants_frame()
+    }
+
+    fn selected_text(&mut self) -> Option<String> {
+        self.state().0.selected_text()
+    }
+
+    fn caret_bounds(&mut self) -> Option<(f32, f32, f32, f32)> {
+        self.state().0.caret_bounds()
+    }
+
+    fn pointer_captured(&mut self) -> bool {
+        self.state().0.gestures.capture.is_captured()
+    }
+
+    fn cursor(&mut self) -> Cursor {
+        let ctx = self.state().0;
+        let cursor_of = |id| {
+            ctx.view
+                .find_shape_by_id(id)
+                .and_then(|idx| ctx.view.cursor_for(idx))
+        };
+
+        // A drag keeps its shape's cursor even when the pointer strays off it,
+        // and shows a grabbing hand unless the shape asked for something else.
+        // A shape holding the pointer capture takes over from the dragged one.
+        let dragged = ctx
+            .gestures
+            .drags
+            .values()
+            .find(|drag| drag.dragging)
+            .and_then(|drag| drag.dragging_shape_id);
+        let captured = ctx.gestures.capture.captured_id();
+        if let Some(id) = captured.or(dragged) {
+            return match cursor_of(id) {
+                None | Some(Cursor::Default) | Some(Cursor::Grab) => Cursor::Grabbing,
+                Some(cursor) => cursor,
+            };
+        }
+
+        ctx.gestures
+            .hover
+            .hover_shape_id
+            .and_then(cursor_of)
+            .unwrap_or_default()
+    }
+}
+
+fn handle_mouse_event<T: App>(app: &mut T, event: MouseEvent) -> bool {
+    let x = event.x as f32;
+    let y = event.y as f32;
+    let info = ui::gesture::GestureInfo::from(&event);
+    let (ctx, state) = app.state();
+    let capture = ctx.gestures.capture.captured_id();
+    ctx.scheduler.advance(event.timestamp);
+
+    // Handle wheel event
+    if event.event_type == EventType::Wheel {
+        // Trackpad pinches arrive as ctrl+wheel and feed the pinch callback
+        if event.modifiers.ctrl && wheel_pinch(ctx, state, &event) {
+            return true;
+        }
+
+        let view = &mut ctx.view;
+        let captured = capture.and_then(|id| view.find_shape_by_id(id).map(|idx| (idx, id)));
+        let hits = match captured {
+            Some(captured) => vec![captured],
+            None => view.hit_test_all(x, y),
+        };
+        let mut scroll = ui::gesture::ScrollEvent::new(
+            Point::new(x, y),
+            event.delta_x as f32,
+            event.delta_y as f32,
+            event.delta_z as f32,
+            event.delta_mode,
+        );
+        let mut handled = false;
+
+        let mut shapes = Vec::new();
+        std::mem::swap(&mut shapes, &mut view.shapes);
+
+        // Deliver from the topmost shape down until a handler stops propagation
+        for (idx, _) in hits {
+            if shapes[idx].run_on_scroll(state, &mut scroll) {
+                handled = true;
+                if scroll.is_propagation_stopped() {
+                    break;
                 }
+            }
+        }
 
-                std::mem::swap(&mut shapes, &mut view.shapes);
-                return true;
+        std::mem::swap(&mut shapes, &mut view.shapes);
+        return handled;
+    }
+
+    // A second touch pointer turns an active touch drag into a pinch
+    if event.event_type == EventType::PointerDown
+        && event.pointer_type == PointerType::Touch
+        && !ctx.gestures.pinch.is_active()
+        && start_touch_pinch(ctx, state, event.pointer_id, Point::new(x, y), &info)
+    {
+        return true;
+    }
+
+    // Pointers taking part in a pinch only drive the pinch
+    if ctx.gestures.pinch.has_pointer(event.pointer_id) {
+        return match event.event_type {
+            EventType::PointerMove => {
+                ctx.gestures
+                    .pinch
+                    .move_pointer(event.pointer_id, Point::new(x, y));
+                deliver_pinch(ctx, state, ui::gesture::DragPhase::Move)
             }
-            return false;
+            EventType::PointerUp => {
+                let delivered = deliver_pinch(ctx, state, ui::gesture::DragPhase::End);
+                ctx.gestures.pinch = PinchState::default();
+                delivered
+            }
+            EventType::PointerCancel | EventType::PointerLeave => {
+                let delivered = deliver_pinch(ctx, state, ui::gesture::DragPhase::Cancel);
+                ctx.gestures.pinch = PinchState::default();
+                delivered
+            }
+            _ => false,
+        };
+    }
+
+    let view = &mut ctx.view;
+
+    // Handle mouse down event
+    if matches!(
+        event.event_type,
+        EventType::MouseDown | EventType::PointerDown
+    ) {
+        // Pressing on a focusable shape focuses it, pressing anywhere else clears focus
+        let focus_id = view
+            .hit_test_captured(capture, x, y)
+            .filter(|&(idx, _)| view.shapes[idx].focusable)
+            .map(|(_, id)| id);
+        let focus_changed = ctx.focus.focused_shape_id != focus_id;
+        ctx.focus.focused_shape_id = focus_id;
+        let ring_hidden = std::mem::take(&mut ctx.focus.visible);
+        let tooltip_hidden = ctx.gestures.hover.dismiss_tooltip();
+
+        // Pressing in a text field places the caret, or extends the selection with shift
+        let text_hit = view.hit_test_captured(capture, x, y).and_then(|(idx, id)| {
+            match &view.shapes[idx].shape_type {
+                ui::ShapeType::TextInput(input) => Some((id, input)),
+                _ => None,
+            }
+        });
+        let caret_placed = text_hit.is_some();
+        if let Some((id, input)) = text_hit {
+            let position = input.position_at_point(Point::new(x, y));
+            let mut edit = ctx
+                .text_edit
+                .take()
+                .filter(|edit| edit.id == id && event.modifiers.shift)
+                .unwrap_or_else(|| ui::TextEditState::new(id, position));
+            edit.composition = None;
+            edit.move_to(position, true);
+            ctx.text_edit = Some(edit);
+        }
+
+        // Hit test the view to check if any interactive elements were clicked
+        if let Some((idx, id)) = view.hit_test_captured(capture, x, y) {
+            // Store drag start position and the element that received mouse down,
+            // keyed by pointer so several pointers can drag at once. Whether this
+            // becomes a click or a drag is decided once the pointer moves or lifts.
+            ctx.gestures.drags.insert(
+                event.pointer_id,
+                DragState {
+                    start_x: Some(x),
+                    start_y: Some(y),
+                    dragging_shape_id: Some(id),
+                    mouse_down_id: Some(id),
+                    current_x: Some(x),
+                    current_y: Some(y),
+                    pointer_type: event.pointer_type,
+                    start_time: Some(event.timestamp),
+                    long_press_pending: view.shapes[idx].on_long_press.is_some(),
+                    long_pressed: false,
+                    dragging: false,
+                },
+            );
+        }
+        return focus_changed || ring_hidden || tooltip_hidden || caret_placed;
+    }
+
+    // A cancelled pointer or one leaving the window aborts its drag without a click
+    if matches!(
+        event.event_type,
+        EventType::PointerCancel | EventType::PointerLeave
+    ) {
+        let tooltip_hidden = ctx.gestures.hover.dismiss_tooltip();
+        let drags = ctx
+            .gestures
+            .drags
+            .remove_entry(&event.pointer_id)
+            .into_iter()
+            .collect();
+
+        let mut shapes = Vec::new();
+        std::mem::swap(&mut shapes, &mut view.shapes);
+        let cancelled = cancel_drags(&shapes, state, drags);
+        std::mem::swap(&mut shapes, &mut view.shapes);
+
+        // Capture is released implicitly once no pointer is pressed
+        if ctx.gestures.drags.is_empty() {
+            ctx.release_pointer();
         }
+        return cancelled || tooltip_hidden;
+    }
 
-        // Handle mouse up event
-        if event.event_type == EventType::MouseUp {
-            // Check if we released on the same shape that we started on (click behavior)
-            let current_hit = view.hit_test_with_id(x, y);
-            let drag = &ctx.gestures.drag;
-
-            if let (Some(drag_id), Some(start_x), Some(start_y), Some(down_id)) = (
-                drag.dragging_shape_id,
-                drag.start_x,
-                drag.start_y,
-                drag.mouse_down_id,
-            ) {
-                // Find the current index of the shape with dragging_shape_id
-                if let Some(drag_idx) = view.find_shape_by_id(drag_id) {
-                    let mut shapes = Vec::new();
-                    std::mem::swap(&mut shapes, &mut view.shapes);
-
-                    // Notify the shape of drag end
+    // Handle mouse up event
+    if matches!(event.event_type, EventType::MouseUp | EventType::PointerUp) {
+        let Some(drag) = ctx.gestures.drags.remove(&event.pointer_id) else {
+            return false;
+        };
+
+        // Check if we released on the same shape that we started on (click behavior)
+        let current_hit = view.hit_test_captured(capture, x, y);
+
+        if let (Some(drag_id), Some(start_x), Some(start_y), Some(down_id)) = (
+            drag.dragging_shape_id,
+            drag.start_x,
+            drag.start_y,
+            drag.mouse_down_id,
+        ) {
+            // Find the current index of the shape with dragging_shape_id
+            if let Some(drag_idx) = view.find_shape_by_id(drag_id) {
+                let click_path = current_hit.map(|(idx, _)| view.propagation_path(idx));
+                let mut shapes = Vec::new();
+                std::mem::swap(&mut shapes, &mut view.shapes);
+
+                // Notify the shape of drag end, if the press turned into a drag
+                if drag.dragging {
                     shapes[drag_idx].run_on_drag(
                         state,
                         ui::gesture::DragPhase::End,
                         ui::gesture::Point::new(start_x, start_y),
                         ui::gesture::Point::new(x, y),
+                        &info,
                     );
+                }
 
-                    // If mouse up is on the same element as mouse down, trigger click
-                    if let Some((down_idx, _)) = current_hit {
-                        if current_hit.map(|(_, id)| id) == Some(down_id) {
-                            shapes[down_idx].run_on_click(state);
+                // If mouse up is on the same element as mouse down, trigger click,
+                // unless the press was already consumed by a drag or a long press
+                if let (Some((down_idx, _)), Some(path)) = (current_hit, click_path) {
+                    if current_hit.map(|(_, id)| id) == Some(down_id)
+                        && !drag.dragging
+                        && !drag.long_pressed
+                    {
+                        let click_count = ctx.gestures.click.register(
+                            down_id,
+                            Point::new(x, y),
+                            event.timestamp,
+                            &ctx.gestures.config,
+                        );
+                        let info = ui::gesture::GestureInfo {
+                            click_count,
+                            ..info
+                        };
+                        ctx.default_prevented |= dispatch_click(&shapes, &path, state, info);
+                        if click_count == 2 {
+                            shapes[down_idx].run_on_double_click(state);
                         }
                     }
-
-                    std::mem::swap(&mut shapes, &mut view.shapes);
                 }
-            }
 
-            ctx.reset_interaction();
+                std::mem::swap(&mut shapes, &mut view.shapes);
+            }
+        }
 
-            return true;
+        // Capture is released implicitly once no pointer is pressed
+        if ctx.gestures.drags.is_empty() {
+            ctx.release_pointer();
         }
 
-        // Handle mouse move event
-        if event.event_type == EventType::MouseMove {
-            // Handle hover effect
-            let hover_hit = view.hit_test_with_id(x, y);
-            let current_hover_id = ctx.gestures.hover.hover_shape_id;
-            let hover_id = hover_hit.map(|(_, id)| id);
+        return true;
+    }
 
-            // Always handle hover effects, even during drags
-            if hover_id != current_hover_id {
-                let mut shapes = Vec::new();
-                std::mem::swap(&mut shapes, &mut view.shapes);
-                if current_hover_id.is_some() {
-                    for shape in &shapes {
-                        if Some(shape.id) != hover_hit.map(|h| h.1) {
-                            shape.run_on_hover(state, false, Point::new(x, y));
-                        }
+    // Handle mouse move event
+    if matches!(
+        event.event_type,
+        EventType::MouseMove | EventType::PointerMove
+    ) {
+        // Handle hover effect
+        let hover_hit = view.hit_test_captured(capture, x, y);
+        let current_hover_id = ctx.gestures.hover.hover_shape_id;
+        let hover_id = hover_hit.map(|(_, id)| id);
+        let hover_changed = hover_id != current_hover_id;
+
+        // Always handle hover effects, even during drags
+        if hover_changed {
+            let mut shapes = Vec::new();
+            std::mem::swap(&mut shapes, &mut view.shapes);
+            if current_hover_id.is_some() {
+                for shape in &shapes {
+                    if Some(shape.id) != hover_hit.map(|h| h.1) {
+                        shape.run_on_hover(state, false, Point::new(x, y), &info);
                     }
                 }
+            }
 
-                // Call on_hover for the new shape
-                if let Some((idx, id)) = hover_hit {
-                    shapes[idx].run_on_hover(state, true, ui::gesture::Point::new(x, y));
-                    ctx.gestures.hover.hover_shape_id = Some(id);
-                } else {
-                    ctx.gestures.hover.hover_shape_id = None;
-                }
+            // Call on_hover for the new shape
+            if let Some((idx, id)) = hover_hit {
+                shapes[idx].run_on_hover(state, true, ui::gesture::Point::new(x, y), &info);
+                ctx.gestures.hover.hover_shape_id = Some(id);
+            } else {
+                ctx.gestures.hover.hover_shape_id = None;
+            }
 
-                std::mem::swap(&mut shapes, &mut view.shapes);
+            std::mem::swap(&mut shapes, &mut view.shapes);
 
-                // Return true to indicate we processed a hover event
-                return true;
+            // Entering a shape restarts the tooltip delay
+            let hover = &mut ctx.gestures.hover;
+            hover.tooltip_visible = false;
+            hover.since = hover.hover_shape_id.map(|_| event.timestamp);
+        }
+
+        // A visible tooltip stays where it was shown
+        if !ctx.gestures.hover.tooltip_visible {
+            ctx.gestures.hover.point = Some(Point::new(x, y));
+        }
+
+        // Moving too far from the press point rules out a long press
+        if let Some(drag) = ctx.gestures.drags.get_mut(&event.pointer_id) {
+            drag.current_x = Some(x);
+            drag.current_y = Some(y);
+            if let (Some(start_x), Some(start_y)) = (drag.start_x, drag.start_y) {
+                if Point::new(start_x, start_y).distance(&Point::new(x, y))
+                    > ctx.gestures.config.long_press_distance
+                {
+                    drag.long_press_pending = false;
+                }
             }
+        }
 
-            // Handle dragging
-            let drag = &ctx.gestures.drag;
-            if let (Some(drag_id), Some(start_x), Some(start_y)) =
-                (drag.dragging_shape_id, drag.start_x, drag.start_y)
+        // Dragging from a press in a text field selects text
+        let selecting = ctx
+            .gestures
+            .drags
+            .get(&event.pointer_id)
+            .and_then(|drag| drag.mouse_down_id)
+            .filter(|&id| ctx.text_edit.as_ref().is_some_and(|edit| edit.id == id))
+            .and_then(|id| view.find_shape_by_id(id));
+        if let Some(idx) = selecting {
+            if let (ui::ShapeType::TextInput(input), Some(edit)) =
+                (&view.shapes[idx].shape_type, &mut ctx.text_edit)
             {
-                // Find the current index of the shape with dragging_shape_id
-                if let Some(idx) = view.find_shape_by_id(drag_id) {
-                    let mut shapes = Vec::new();
-                    std::mem::swap(&mut shapes, &mut view.shapes);
+                let position = input.position_at_point(Point::new(x, y));
+                if edit.caret != position {
+                    edit.move_to(position, true);
+                    return true;
+                }
+            }
+        }
+
+        // Handle dragging for this pointer
+        let slop = ctx.gestures.config.drag_slop;
+        let drag = ctx.gestures.drags.get_mut(&event.pointer_id);
+        if let Some((drag, drag_id, start_x, start_y)) = drag.and_then(|drag| {
+            let (id, start_x, start_y) = (drag.dragging_shape_id?, drag.start_x?, drag.start_y?);
+            Some((drag, id, start_x, start_y))
+        }) {
+            // Find the current index of the shape with dragging_shape_id
+            if let Some(idx) = view.find_shape_by_id(drag_id) {
+                // A press only becomes a drag once it moves past the slop distance,
+                // and only on shapes that handle drags. Until then it may still be a click.
+                let starting = !drag.dragging;
+                if starting {
+                    let moved = Point::new(start_x, start_y).distance(&Point::new(x, y));
+                    if view.shapes[idx].on_drag.is_none() || moved <= slop {
+                        return hover_changed;
+                    }
+                    drag.dragging = true;
+                    drag.long_press_pending = false;
+                }
+
+                let mut shapes = Vec::new();
+                std::mem::swap(&mut shapes, &mut view.shapes);
+                if starting {
                     shapes[idx].run_on_drag(
                         state,
-                        ui::gesture::DragPhase::Move,
+                        ui::gesture::DragPhase::Start,
                         ui::gesture::Point::new(start_x, start_y),
-                        ui::gesture::Point::new(x, y),
+                        ui::gesture::Point::new(start_x, start_y),
+                        &info,
                     );
-                    std::mem::swap(&mut shapes, &mut view.shapes);
-
-                    return true;
                 }
+                shapes[idx].run_on_drag(
+                    state,
+                    ui::gesture::DragPhase::Move,
+                    ui::gesture::Point::new(start_x, start_y),
+                    ui::gesture::Point::new(x, y),
+                    &info,
+                );
+                std::mem::swap(&mut shapes, &mut view.shapes);
+
+                return true;
             }
         }
 
-        false
+        // Return true to indicate we processed a hover event
+        return hover_changed;
     }
 
-    fn render(&mut self, dimensions: Dimensions) -> String {
-        self.state().0.set_dimensions(dimensions);
-        self.state().0.clear();
-        self.view(dimensions);
-        self.state().0.view.render(dimensions)
+    false
+}
+
+/// Close the history step opened for an event, keeping it open while a drag or pinch continues
+fn end_history_step<S>(ctx: &AppCtx<S>) {
+    let gesturing =
+        ctx.gestures.drags.values().any(|drag| drag.dragging) || ctx.gestures.pinch.is_active();
+    ctx.history.hold_for_gesture(gesturing);
+    ctx.history.end_group();
+}
+
+fn poll_gestures<T: App>(app: &mut T, now: f64) -> bool {
+    let (ctx, state) = app.state();
+    let view = &mut ctx.view;
+    let delay = ctx.gestures.config.long_press_delay;
+    let mut changed = false;
+
+    let mut shapes = Vec::new();
+    std::mem::swap(&mut shapes, &mut view.shapes);
+
+    // Fire long presses for pointers held still past the delay
+    for drag in ctx.gestures.drags.values_mut() {
+        let due = drag.start_time.is_some_and(|start| now >= start + delay);
+        if !drag.long_press_pending || !due {
+            continue;
+        }
+        drag.long_press_pending = false;
+        drag.long_pressed = true;
+
+        let idx = drag
+            .mouse_down_id
+            .and_then(|id| shapes.iter().position(|shape| shape.id == id));
+        if let (Some(idx), Some(start_x), Some(start_y)) = (idx, drag.start_x, drag.start_y) {
+            shapes[idx].run_on_long_press(state, Point::new(start_x, start_y));
+            changed = true;
+        }
+    }
+
+    std::mem::swap(&mut shapes, &mut view.shapes);
+
+    // Wheel events have no release, so a wheel pinch ends once the wheel goes quiet
+    let wheel_pinch_due = ctx
+        .gestures
+        .pinch
+        .last_wheel_time
+        .is_some_and(|last| now >= last + ctx.gestures.config.wheel_pinch_end_delay);
+    if wheel_pinch_due {
+        changed |= deliver_pinch(ctx, state, ui::gesture::DragPhase::End);
+        ctx.gestures.pinch = PinchState::default();
+    }
+
+    changed
+}
+
+/// Show the hovered shape's tooltip once the pointer has rested on it long enough
+fn show_due_tooltip<S>(ctx: &mut AppCtx<S>, now: f64) -> bool {
+    if !ctx
+        .tooltip_deadline()
+        .is_some_and(|deadline| now >= deadline)
+    {
+        return false;
+    }
+    ctx.gestures.hover.since = None;
+    ctx.gestures.hover.tooltip_visible = true;
+    true
+}
+
+/// Start a pinch between `pointer_id` and another touch pointer that is already down
+fn start_touch_pinch<S>(
+    ctx: &mut AppCtx<S>,
+    state: &mut S,
+    pointer_id: i32,
+    point: Point,
+    info: &ui::gesture::GestureInfo,
+) -> bool {
+    let other = ctx.gestures.drags.iter().find_map(|(&id, drag)| {
+        if id == pointer_id || drag.pointer_type != PointerType::Touch {
+            return None;
+        }
+        Some((id, Point::new(drag.current_x?, drag.current_y?)))
+    });
+    let Some((other_id, other_point)) = other else {
+        return false;
+    };
+
+    let centroid = Point::new(
+        (point.x + other_point.x) / 2.0,
+        (point.y + other_point.y) / 2.0,
+    );
+    let target = ctx
+        .view
+        .hit_test_all(centroid.x, centroid.y)
+        .into_iter()
+        .find(|&(idx, _)| ctx.view.shapes[idx].on_pinch.is_some());
+    let Some((_, target_id)) = target else {
+        return false;
+    };
+
+    // The first finger's drag ends here, its movement now belongs to the pinch
+    if let Some(drag) = ctx
+        .gestures
+        .drags
+        .remove(&other_id)
+        .filter(|drag| drag.dragging)
+    {
+        let drag_idx = drag
+            .dragging_shape_id
+            .and_then(|id| ctx.view.find_shape_by_id(id));
+        if let (Some(idx), Some(start_x), Some(start_y)) = (drag_idx, drag.start_x, drag.start_y) {
+            let mut shapes = Vec::new();
+            std::mem::swap(&mut shapes, &mut ctx.view.shapes);
+            shapes[idx].run_on_drag(
+                state,
+                ui::gesture::DragPhase::End,
+                Point::new(start_x, start_y),
+                other_point,
+                info,
+            );
+            std::mem::swap(&mut shapes, &mut ctx.view.shapes);
+        }
+    }
+
+    ctx.gestures.pinch = PinchState::touch(target_id, (other_id, other_point), (pointer_id, point));
+    deliver_pinch(ctx, state, ui::gesture::DragPhase::Start)
+}
+
+/// Feed a ctrl+wheel event into the pinch gesture of the shape under the pointer
+fn wheel_pinch<S>(ctx: &mut AppCtx<S>, state: &mut S, event: &MouseEvent) -> bool {
+    let point = Point::new(event.x as f32, event.y as f32);
+
+    if ctx.gestures.pinch.last_wheel_time.is_none() {
+        if ctx.gestures.pinch.is_active() {
+            return false;
+        }
+        let target = ctx
+            .view
+            .hit_test_all(point.x, point.y)
+            .into_iter()
+            .find(|&(idx, _)| ctx.view.shapes[idx].on_pinch.is_some());
+        let Some((_, target_id)) = target else {
+            return false;
+        };
+
+        ctx.gestures.pinch = PinchState::wheel(target_id, point, event.timestamp);
+        deliver_pinch(ctx, state, ui::gesture::DragPhase::Start);
+    }
+
+    let delta = match event.delta_mode {
+        DeltaMode::Pixel => event.delta_y as f32,
+        DeltaMode::Line => event.delta_y as f32 * 16.0,
+        DeltaMode::Page => event.delta_y as f32 * ctx.dimensions.height as f32,
+    };
+    let pinch = &mut ctx.gestures.pinch;
+    pinch.wheel_scale *= (-delta * ctx.gestures.config.wheel_zoom_speed).exp();
+    pinch.wheel_point = Some(point);
+    pinch.last_wheel_time = Some(event.timestamp);
+
+    deliver_pinch(ctx, state, ui::gesture::DragPhase::Move);
+    true
+}
+
+/// Run the pinch callback of the shape receiving the current pinch
+fn deliver_pinch<S>(ctx: &mut AppCtx<S>, state: &mut S, phase: ui::gesture::DragPhase) -> bool {
+    let pinch = &ctx.gestures.pinch;
+    let (Some(idx), Some(gesture)) = (
+        pinch.shape_id.and_then(|id| ctx.view.find_shape_by_id(id)),
+        pinch.gesture(),
+    ) else {
+        return false;
+    };
+
+    let mut shapes = Vec::new();
+    std::mem::swap(&mut shapes, &mut ctx.view.shapes);
+    shapes[idx].run_on_pinch(state, phase, gesture);
+    std::mem::swap(&mut shapes, &mut ctx.view.shapes);
+    true
+}
+
+/// Dispatch a click along a propagation path, target first and root last
+///
+/// Capture callbacks run from the root down to the target, then click callbacks run from
+/// the target back up to the root, until a callback stops propagation.
+/// Returns whether a callback prevented the default action.
+fn dispatch_click<S>(
+    shapes: &[ui::Shape<S>],
+    path: &[usize],
+    state: &mut S,
+    info: ui::gesture::GestureInfo,
+) -> bool {
+    let Some(&target_idx) = path.first() else {
+        return false;
+    };
+    let mut event = ui::gesture::EventCtx::new(info, shapes[target_idx].id);
+
+    for &idx in path.iter().skip(1).rev() {
+        event.enter(ui::gesture::EventPhase::Capture, shapes[idx].id);
+        shapes[idx].run_on_click_capture(state, &mut event);
+        if event.is_propagation_stopped() {
+            return event.is_default_prevented();
+        }
+    }
+
+    event.enter(ui::gesture::EventPhase::Target, shapes[target_idx].id);
+    shapes[target_idx].run_on_click_capture(state, &mut event);
+    if !event.is_propagation_stopped() {
+        shapes[target_idx].run_on_click(state, &mut event);
+    }
+
+    for &idx in path.iter().skip(1) {
+        if event.is_propagation_stopped() {
+            break;
+        }
+        event.enter(ui::gesture::EventPhase::Bubble, shapes[idx].id);
+        shapes[idx].run_on_click(state, &mut event);
+    }
+
+    event.is_default_prevented()
+}
+
+/// Notify dragged shapes that their drags were cancelled
+fn cancel_drags<S>(shapes: &[ui::Shape<S>], state: &mut S, drags: Vec<(i32, DragState)>) -> bool {
+    let mut cancelled = false;
+
+    for (pointer_id, drag) in drags.into_iter().filter(|(_, drag)| drag.dragging) {
+        let shape = drag
+            .dragging_shape_id
+            .and_then(|id| shapes.iter().find(|shape| shape.id == id));
+        if let (Some(shape), Some(start_x), Some(start_y), Some(current_x), Some(current_y)) = (
+            shape,
+            drag.start_x,
+            drag.start_y,
+            drag.current_x,
+            drag.current_y,
+        ) {
+            let info = ui::gesture::GestureInfo {
+                pointer_id,
+                pointer_type: drag.pointer_type,
+                ..Default::default()
+            };
+            shape.run_on_drag(
+                state,
+                ui::gesture::DragPhase::Cancel,
+                Point::new(start_x, start_y),
+                Point::new(current_x, current_y),
+                &info,
+            );
+            cancelled = true;
+        }
+    }
+
+    cancelled
+}
+
+/// Cancel every drag and pinch in progress
+fn cancel_interactions<S>(ctx: &mut AppCtx<S>, state: &mut S) -> bool {
+    let mut cancelled = false;
+    if ctx.gestures.pinch.is_active() {
+        cancelled |= deliver_pinch(ctx, state, ui::gesture::DragPhase::Cancel);
+        ctx.gestures.pinch = PinchState::default();
+    }
+
+    let drags = ctx.gestures.drags.drain().collect();
+    ctx.release_pointer();
+    let mut shapes = Vec::new();
+    std::mem::swap(&mut shapes, &mut ctx.view.shapes);
+    cancelled |= cancel_drags(&shapes, state, drags);
+    std::mem::swap(&mut shapes, &mut ctx.view.shapes);
+
+    cancelled
+}
+
+fn handle_key_event<T: App>(app: &mut T, event: KeyEvent) -> bool {
+    let (ctx, state) = app.state();
+    ctx.scheduler.advance(event.timestamp);
+
+    // Escape aborts any drag or pinch in progress
+    let interacting =
+        ctx.gestures.pinch.is_active() || ctx.gestures.drags.values().any(|drag| drag.dragging);
+    if event.key == "Escape" && event.event_type == EventType::KeyDown && interacting {
+        return cancel_interactions(ctx, state);
+    }
+
+    let view = &mut ctx.view;
+
+    // Tab moves focus between focusable shapes instead of reaching a handler
+    if event.key == "Tab" {
+        if event.event_type != EventType::KeyDown {
+            return false;
+        }
+        let next = view.next_focusable(ctx.focus.focused_shape_id, event.modifiers.shift);
+        let changed = next != ctx.focus.focused_shape_id || ctx.focus.visible != next.is_some();
+        ctx.focus.focused_shape_id = next;
+        ctx.focus.visible = next.is_some();
+        // Tabbing past the ends leaves the browser to move focus out of the app
+        ctx.default_prevented = next.is_some();
+        return changed;
+    }
+
+    let Some(focused_id) = ctx.focus.focused_shape_id else {
+        return false;
+    };
+    let Some(idx) = view.find_shape_by_id(focused_id) else {
+        return false;
+    };
+
+    let path = view.propagation_path(idx);
+    let mut shapes = Vec::new();
+    std::mem::swap(&mut shapes, &mut view.shapes);
+    let handled = shapes[idx].run_on_key(state, &event);
+    std::mem::swap(&mut shapes, &mut view.shapes);
+    if handled {
+        return true;
+    }
+
+    // Text fields edit their value with the keys their key handler left alone
+    if matches!(view.shapes[idx].shape_type, ui::ShapeType::TextInput(_)) {
+        return edit_text_input(ctx, state, |edit, value, multiline| {
+            edit.handle_key(value, multiline, &event)
+        });
+    }
+
+    // Enter and Space activate the focused shape like a click, unless its key
+    // handler already used the key
+    let activates = event.event_type == EventType::KeyDown
+        && (event.key == "Enter" || (event.key == " " && !event.repeat));
+    let clickable = path.iter().any(|&idx| {
+        view.shapes[idx].on_click.is_some() || view.shapes[idx].on_click_capture.is_some()
+    });
+    if !activates || !clickable {
+        return false;
+    }
+
+    let info = ui::gesture::GestureInfo {
+        modifiers: event.modifiers,
+        click_count: 1,
+        ..Default::default()
+    };
+    let mut shapes = Vec::new();
+    std::mem::swap(&mut shapes, &mut view.shapes);
+    dispatch_click(&shapes, &path, state, info);
+    std::mem::swap(&mut shapes, &mut view.shapes);
+
+    // The key was used, so the page must not scroll or submit
+    ctx.default_prevented = true;
+    true
+}
+
+/// Apply an edit to the focused text field and hand a changed value to its `on_input`
+///
+/// Returns whether the field used the event.
+fn edit_text_input<S>(
+    ctx: &mut AppCtx<S>,
+    state: &mut S,
+    edit: impl FnOnce(&mut ui::TextEditState, &str, bool) -> ui::EditResult,
+) -> bool {
+    let Some(mut edit_state) = ctx.text_edit_state() else {
+        return false;
+    };
+    let Some(idx) = ctx.view.find_shape_by_id(edit_state.id) else {
+        return false;
+    };
+    let ui::ShapeType::TextInput(input) = &mut ctx.view.shapes[idx].shape_type else {
+        return false;
+    };
+
+    let result = edit(&mut edit_state, &input.value, input.multiline);
+    if let ui::EditResult::Changed(value) = &result {
+        // Later events before the next render see the edited value too
+        input.value = value.clone();
+    }
+    ctx.text_edit = Some(edit_state);
+
+    match result {
+        ui::EditResult::Ignored => false,
+        ui::EditResult::Moved => {
+            ctx.default_prevented = true;
+            true
+        }
+        ui::EditResult::Changed(value) => {
+            ctx.default_prevented = true;
+            let mut shapes = Vec::new();
+            std::mem::swap(&mut shapes, &mut ctx.view.shapes);
+            shapes[idx].run_on_input(state, value);
+            std::mem::swap(&mut shapes, &mut ctx.view.shapes);
+            true
+        }
     }
 }
 
@@ -262,6 +1460,165 @@ pub struct DragState {
     pub dragging_shape_id: Option<u64>,
     /// ID of shape that received mouse down
     pub mouse_down_id: Option<u64>,
+    /// Latest X coordinate of the pointer
+    pub current_x: Option<f32>,
+    /// Latest Y coordinate of the pointer
+    pub current_y: Option<f32>,
+    /// Kind of device driving the drag
+    pub pointer_type: PointerType,
+    /// Time the pointer was pressed, in milliseconds
+    pub start_time: Option<f64>,
+    /// Whether a long press may still fire for this pointer
+    pub long_press_pending: bool,
+    /// Whether a long press already fired for this pointer
+    pub long_pressed: bool,
+    /// Whether the pointer moved past the slop distance and is dragging
+    pub dragging: bool,
+}
+
+/// State for tracking pinch and rotate gestures
+#[derive(Clone, Debug)]
+pub struct PinchState {
+    /// ID of shape receiving the pinch
+    pub shape_id: Option<u64>,
+    /// Touch pointers taking part in the pinch, with their latest positions
+    pub pointers: Vec<(i32, Point)>,
+    /// Distance between the touch pointers when the pinch started
+    pub start_distance: f32,
+    /// Angle of the line between the touch pointers when the pinch started, in radians
+    pub start_angle: f32,
+    /// Scale accumulated from ctrl+wheel events
+    pub wheel_scale: f32,
+    /// Pointer position of the latest ctrl+wheel event
+    pub wheel_point: Option<Point>,
+    /// Time of the latest ctrl+wheel event, in milliseconds
+    pub last_wheel_time: Option<f64>,
+}
+
+impl Default for PinchState {
+    fn default() -> Self {
+        Self {
+            shape_id: None,
+            pointers: Vec::new(),
+            start_distance: 0.0,
+            start_angle: 0.0,
+            wheel_scale: 1.0,
+            wheel_point: None,
+            last_wheel_time: None,
+        }
+    }
+}
+
+impl PinchState {
+    /// Start a pinch driven by two touch pointers
+    pub fn touch(shape_id: u64, first: (i32, Point), second: (i32, Point)) -> Self {
+        Self {
+            shape_id: Some(shape_id),
+            pointers: vec![first, second],
+            start_distance: first.1.distance(&second.1),
+            start_angle: (second.1.y - first.1.y).atan2(second.1.x - first.1.x),
+            ..Self::default()
+        }
+    }
+
+    /// Start a pinch driven by ctrl+wheel events
+    pub fn wheel(shape_id: u64, point: Point, time: f64) -> Self {
+        Self {
+            shape_id: Some(shape_id),
+            wheel_point: Some(point),
+            last_wheel_time: Some(time),
+            ..Self::default()
+        }
+    }
+
+    /// Whether a pinch is in progress
+    pub fn is_active(&self) -> bool {
+        self.shape_id.is_some()
+    }
+
+    /// Whether the given pointer takes part in the pinch
+    pub fn has_pointer(&self, pointer_id: i32) -> bool {
+        self.pointers.iter().any(|&(id, _)| id == pointer_id)
+    }
+
+    /// Update the position of a pointer taking part in the pinch
+    pub fn move_pointer(&mut self, pointer_id: i32, point: Point) {
+        if let Some(entry) = self.pointers.iter_mut().find(|(id, _)| *id == pointer_id) {
+            entry.1 = point;
+        }
+    }
+
+    /// Compute the current scale, rotation and centroid of the pinch
+    pub fn gesture(&self) -> Option<ui::gesture::PinchGesture> {
+        if let Some(centroid) = self.wheel_point {
+            return Some(ui::gesture::PinchGesture {
+                scale: self.wheel_scale,
+                rotation: 0.0,
+                centroid,
+            });
+        }
+
+        let [(_, a), (_, b)] = self.pointers.as_slice() else {
+            return None;
+        };
+        let angle = (b.y - a.y).atan2(b.x - a.x);
+        let mut rotation = angle - self.start_angle;
+        if rotation > std::f32::consts::PI {
+            rotation -= std::f32::consts::TAU;
+        } else if rotation < -std::f32::consts::PI {
+            rotation += std::f32::consts::TAU;
+        }
+
+        Some(ui::gesture::PinchGesture {
+            scale: if self.start_distance > 0.0 {
+                a.distance(b) / self.start_distance
+            } else {
+                1.0
+            },
+            rotation,
+            centroid: Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0),
+        })
+    }
+}
+
+/// State for counting consecutive clicks
+#[derive(Default, Clone, Debug)]
+pub struct ClickState {
+    /// ID of shape that received the last click
+    pub last_shape_id: Option<u64>,
+    /// Position of the last click
+    pub last_point: Option<Point>,
+    /// Time of the last click, in milliseconds
+    pub last_time: Option<f64>,
+    /// Number of consecutive clicks so far
+    pub count: u32,
+}
+
+impl ClickState {
+    /// Record a click and return its position in the current click sequence
+    ///
+    /// Returns 1 for a single click, 2 for a double click and so on.
+    pub fn register(
+        &mut self,
+        shape_id: u64,
+        point: Point,
+        time: f64,
+        config: &ui::gesture::GestureConfig,
+    ) -> u32 {
+        let continues = self.last_shape_id == Some(shape_id)
+            && self
+                .last_time
+                .is_some_and(|last| time - last <= config.multi_click_time)
+            && self
+                .last_point
+                .is_some_and(|last| last.distance(&point) <= config.multi_click_distance);
+
+        self.count = if continues { self.count + 1 } else { 1 };
+        self.last_shape_id = Some(shape_id);
+        self.last_point = Some(point);
+        self.last_time = Some(time);
+        self.count
+    }
 }
 
 /// State for tracking hover operations
@@ -269,6 +1626,30 @@ pub struct DragState {
 pub struct HoverState {
     /// ID of shape being hovered
     pub hover_shape_id: Option<u64>,
+    /// When the pointer entered the hovered shape, until the tooltip is shown or dismissed
+    pub since: Option<f64>,
+    /// Last pointer position, or where the tooltip was shown while it is visible
+    pub point: Option<Point>,
+    /// Whether the hovered shape's tooltip is showing
+    pub tooltip_visible: bool,
+}
+
+impl HoverState {
+    /// Hide the tooltip and keep it hidden until the pointer enters another shape,
+    /// returning whether it was showing
+    pub fn dismiss_tooltip(&mut self) -> bool {
+        self.since = None;
+        std::mem::take(&mut self.tooltip_visible)
+    }
+}
+
+/// State for tracking keyboard focus
+#[derive(Default, Clone, Debug)]
+pub struct FocusState {
+    /// ID of shape that receives keyboard events
+    pub focused_shape_id: Option<u64>,
+    /// Whether focus last moved by keyboard, so the focus ring is drawn
+    pub visible: bool,
 }
 
 #[macro_export]
@@ -293,14 +1674,204 @@ macro_rules! salt_app {
                 }
             }
 
-            pub fn handle_mouse_event(&mut self, event_type: &str, x: f64, y: f64) -> bool {
+            #[allow(clippy::too_many_arguments)]
+            pub fn handle_mouse_event(
+                &mut self,
+                event_type: &str,
+                x: f64,
+                y: f64,
+                pointer_id: i32,
+                pointer_type: &str,
+                pressure: f32,
+                button: i16,
+                buttons: u16,
+                shift: bool,
+                ctrl: bool,
+                alt: bool,
+                meta: bool,
+                timestamp: f64,
+            ) -> Result<bool, JsError> {
+                let event_type = $crate::EventType::parse_pointer(event_type)?;
+                let event = $crate::MouseEvent {
+                    timestamp,
+                    pointer_id,
+                    pointer_type: $crate::PointerType::from(pointer_type),
+                    pressure,
+                    button,
+                    buttons,
+                    modifiers: $crate::Modifiers {
+                        shift,
+                        ctrl,
+                        alt,
+                        meta,
+                    },
+                    ..$crate::MouseEvent::new(event_type, x, y)
+                };
+
+                Ok(self.app.handle_event(event.into()))
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn handle_wheel_event(
+                &mut self,
+                x: f64,
+                y: f64,
+                delta_x: f64,
+                delta_y: f64,
+                delta_z: f64,
+                delta_mode: u32,
+                shift: bool,
+                ctrl: bool,
+                alt: bool,
+                meta: bool,
+                timestamp: f64,
+            ) -> bool {
                 let event = $crate::MouseEvent {
-                    event_type: $crate::EventType::from(event_type),
-                    x,
-                    y,
+                    timestamp,
+                    delta_x,
+                    delta_y,
+                    delta_z,
+                    delta_mode: $crate::DeltaMode::from(delta_mode),
+                    modifiers: $crate::Modifiers {
+                        shift,
+                        ctrl,
+                        alt,
+                        meta,
+                    },
+                    ..$crate::MouseEvent::new($crate::EventType::Wheel, x, y)
+                };
+
+                self.app.handle_event(event.into())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn handle_key_event(
+                &mut self,
+                event_type: &str,
+                key: &str,
+                code: &str,
+                repeat: bool,
+                shift: bool,
+                ctrl: bool,
+                alt: bool,
+                meta: bool,
+                timestamp: f64,
+            ) -> Result<bool, JsError> {
+                let event = $crate::KeyEvent {
+                    event_type: $crate::EventType::parse_key(event_type)?,
+                    key: key.to_string(),
+                    code: code.to_string(),
+                    repeat,
+                    modifiers: $crate::Modifiers {
+                        shift,
+                        ctrl,
+                        alt,
+                        meta,
+                    },
+                    timestamp,
+                };
+
+                Ok(self.app.handle_event(event.into()))
+            }
+
+            pub fn handle_blur(&mut self) -> bool {
+                self.app.handle_event($crate::Event::Blur)
+            }
+
+            pub fn handle_focus_out(&mut self) -> bool {
+                self.app.handle_event($crate::Event::FocusOut)
+            }
+
+            pub fn handle_custom_event(&mut self, name: &str, detail: &str) -> bool {
+                let event = $crate::CustomEvent {
+                    name: name.to_string(),
+                    detail: detail.to_string(),
                 };
 
-                self.app.handle_event(event)
+                self.app.handle_event(event.into())
+            }
+
+            pub fn tick(&mut self, dt: f64) -> bool {
+                self.app.tick(dt)
+            }
+
+            pub fn wants_frame(&mut self) -> bool {
+                self.app.wants_frame()
+            }
+
+            pub fn pointer_captured(&mut self) -> bool {
+                self.app.pointer_captured()
+            }
+
+            pub fn cursor(&mut self) -> String {
+                self.app.cursor().as_css().to_string()
+            }
+
+            pub fn handle_text_event(
+                &mut self,
+                event_type: &str,
+                data: &str,
+            ) -> Result<bool, JsError> {
+                let event = $crate::TextEvent {
+                    event_type: $crate::EventType::parse_text(event_type)?,
+                    data: data.to_string(),
+                };
+
+                Ok(self.app.handle_event(event.into()))
+            }
+
+            pub fn selected_text(&mut self) -> Option<String> {
+                self.app.selected_text()
+            }
+
+            /// Caret bounds as `[min_x, min_y, max_x, max_y]`, while a text field is focused
+            pub fn caret_bounds(&mut self) -> Option<Vec<f32>> {
+                self.app
+                    .caret_bounds()
+                    .map(|(min_x, min_y, max_x, max_y)| vec![min_x, min_y, max_x, max_y])
+            }
+
+            pub fn default_prevented(&mut self) -> bool {
+                self.app.default_prevented()
+            }
+
+            pub fn poll(&mut self, now: f64) -> bool {
+                self.app.poll(now)
+            }
+
+            pub fn next_deadline(&mut self) -> Option<f64> {
+                self.app.next_deadline()
+            }
+
+            pub fn flush(&mut self) {
+                self.app.flush();
+            }
+
+            pub fn start_recording(&mut self) {
+                self.app.start_recording();
+            }
+
+            /// Stop recording and return the session as JSON
+            pub fn stop_recording(&mut self) -> Option<String> {
+                self.app
+                    .stop_recording()
+                    .map(|recording| recording.to_json())
+            }
+
+            /// Replace the app with a fresh one and replay a recorded session into it
+            ///
+            /// Only the steps before `until` are replayed when it is given.
+            pub fn replay(&mut self, json: &str, until: Option<usize>) -> Result<(), JsError> {
+                let recording = $crate::recording::Recording::from_json(json)?;
+                self.app = <$app_type as $crate::AppCore>::new();
+                recording.replay_until(&mut self.app, until.unwrap_or(recording.len()));
+                Ok(())
+            }
+
+            pub fn set_waker(&mut self, callback: $crate::js_sys::Function) {
+                self.app.set_waker(::std::rc::Rc::new(move || {
+                    let _ = callback.call0(&JsValue::NULL);
+                }));
             }
 
             pub fn render_svg(&mut self, width: u32, height: u32) -> String {
@@ -0,0 +1,354 @@
+//! State persistence for Salt applications
+//!
+//! Apps whose state implements serde's `Serialize` and `Deserialize` can opt in
+//! with [`AppCtx::persist`](crate::ui::AppCtx::persist). The state is restored
+//! from a [`Storage`] backend right away, and saved again a short while after
+//! events, timers or tasks change it.
+//!
+//! Stored values are JSON envelopes of the form `{"version": 2, "state": {...}}`.
+//! When the stored version is older than the configured one, the registered
+//! migrations upgrade the state JSON one version at a time before it is loaded.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Default delay between the last change and the save that follows it
+pub const DEFAULT_SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// Default longest wait between the first unsaved change and the save
+pub const DEFAULT_MAX_SAVE_WAIT: Duration = Duration::from_millis(2000);
+
+/// Key-value store that persisted state is written to
+pub trait Storage {
+    /// Read the value stored under `key`
+    fn load(&self, key: &str) -> Result<Option<String>, PersistError>;
+
+    /// Store `value` under `key`, replacing any previous value
+    fn save(&self, key: &str, value: &str) -> Result<(), PersistError>;
+
+    /// Delete the value stored under `key`
+    fn remove(&self, key: &str) -> Result<(), PersistError>;
+}
+
+/// Storage kept in memory, shared between clones
+///
+/// Useful in native tests: keep a clone to inspect what was saved, or to
+/// restore it into a second app.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorage {
+    values: Rc<RefCell<HashMap<String, String>>>,
+}
+
+impl MemoryStorage {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn load(&self, key: &str) -> Result<Option<String>, PersistError> {
+        Ok(self.values.borrow().get(key).cloned())
+    }
+
+    fn save(&self, key: &str, value: &str) -> Result<(), PersistError> {
+        self.values
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), PersistError> {
+        self.values.borrow_mut().remove(key);
+        Ok(())
+    }
+}
+
+/// Storage writing one `<key>.json` file per key into a directory
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    dir: PathBuf,
+}
+
+impl FileStorage {
+    /// Store files in `dir`, which is created on the first save
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+impl Storage for FileStorage {
+    fn load(&self, key: &str) -> Result<Option<String>, PersistError> {
+        match std::fs::read_to_string(self.path(key)) {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(PersistError::Storage(err.to_string())),
+        }
+    }
+
+    fn save(&self, key: &str, value: &str) -> Result<(), PersistError> {
+        std::fs::create_dir_all(&self.dir)
+            .and_then(|_| std::fs::write(self.path(key), value))
+            .map_err(|err| PersistError::Storage(err.to_string()))
+    }
+
+    fn remove(&self, key: &str) -> Result<(), PersistError> {
+        match std::fs::remove_file(self.path(key)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                Err(PersistError::Storage(err.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Storage backed by the browser's `localStorage`
+///
+/// Only usable in the browser.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalStorage;
+
+impl LocalStorage {
+    /// Use the current window's `localStorage`
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn storage(&self) -> Result<web_sys::Storage, PersistError> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or_else(|| PersistError::Storage("localStorage is not available".to_string()))
+    }
+}
+
+impl Storage for LocalStorage {
+    fn load(&self, key: &str) -> Result<Option<String>, PersistError> {
+        self.storage()?
+            .get_item(key)
+            .map_err(|err| PersistError::Storage(format!("{:?}", err)))
+    }
+
+    fn save(&self, key: &str, value: &str) -> Result<(), PersistError> {
+        self.storage()?
+            .set_item(key, value)
+            .map_err(|err| PersistError::Storage(format!("{:?}", err)))
+    }
+
+    fn remove(&self, key: &str) -> Result<(), PersistError> {
+        self.storage()?
+            .remove_item(key)
+            .map_err(|err| PersistError::Storage(format!("{:?}", err)))
+    }
+}
+
+/// Error raised while loading or saving persisted state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PersistError {
+    /// The storage backend failed
+    Storage(String),
+    /// The stored value could not be converted to or from the state
+    Format(String),
+    /// No migration upgrades the stored state from this version
+    MissingMigration(u32),
+    /// The stored state was written by a newer schema version
+    NewerVersion(u32),
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistError::Storage(message) => write!(f, "storage error: {}", message),
+            PersistError::Format(message) => write!(f, "invalid persisted state: {}", message),
+            PersistError::MissingMigration(version) => {
+                write!(f, "no migration from state version {}", version)
+            }
+            PersistError::NewerVersion(version) => {
+                write!(
+                    f,
+                    "persisted state version {} is newer than the app",
+                    version
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for PersistError {}
+
+impl From<serde_json::Error> for PersistError {
+    fn from(err: serde_json::Error) -> Self {
+        PersistError::Format(err.to_string())
+    }
+}
+
+/// Upgrades state JSON written by one schema version to the next
+pub type Migration = fn(Value) -> Value;
+
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    version: u32,
+    state: Value,
+}
+
+/// How and where an app's state is persisted
+pub struct PersistConfig {
+    key: String,
+    storage: Rc<dyn Storage>,
+    version: u32,
+    migrations: HashMap<u32, Migration>,
+    delay: Duration,
+    max_wait: Duration,
+}
+
+impl PersistConfig {
+    /// Persist under `key` in `storage`, at schema version 0
+    pub fn new(key: impl Into<String>, storage: impl Storage + 'static) -> Self {
+        Self {
+            key: key.into(),
+            storage: Rc::new(storage),
+            version: 0,
+            migrations: HashMap::new(),
+            delay: DEFAULT_SAVE_DELAY,
+            max_wait: DEFAULT_MAX_SAVE_WAIT,
+        }
+    }
+
+    /// Set the schema version of the current state struct
+    pub fn version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Register a migration that upgrades state JSON from version `from` to `from + 1`
+    pub fn migration(mut self, from: u32, migration: Migration) -> Self {
+        self.migrations.insert(from, migration);
+        self
+    }
+
+    /// Set how long to wait after the last change before saving
+    pub fn save_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Set the longest wait between the first unsaved change and the save
+    ///
+    /// This bounds how long changes arriving faster than the save delay, such
+    /// as a continuous animation, can hold the save back.
+    pub fn max_save_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    /// Read the stored state, migrating it to the current version
+    pub fn load<S: DeserializeOwned>(&self) -> Result<Option<S>, PersistError> {
+        let Some(stored) = self.storage.load(&self.key)? else {
+            return Ok(None);
+        };
+        let envelope: Envelope = serde_json::from_str(&stored)?;
+        if envelope.version > self.version {
+            return Err(PersistError::NewerVersion(envelope.version));
+        }
+
+        let mut state = envelope.state;
+        for version in envelope.version..self.version {
+            let migrate = self
+                .migrations
+                .get(&version)
+                .ok_or(PersistError::MissingMigration(version))?;
+            state = migrate(state);
+        }
+        Ok(Some(serde_json::from_value(state)?))
+    }
+
+    /// Write the state at the current version
+    pub fn save<S: Serialize>(&self, state: &S) -> Result<(), PersistError> {
+        let envelope = Envelope {
+            version: self.version,
+            state: serde_json::to_value(state)?,
+        };
+        self.storage
+            .save(&self.key, &serde_json::to_string(&envelope)?)
+    }
+
+    /// Delete the stored state
+    pub fn clear(&self) -> Result<(), PersistError> {
+        self.storage.remove(&self.key)
+    }
+}
+
+type Saver<T> = Box<dyn Fn(&T) -> Result<(), PersistError>>;
+
+/// Debounced saving of an app's state, installed by `AppCtx::persist`
+pub struct Persistence<T> {
+    save: Saver<T>,
+    delay_ms: f64,
+    max_wait_ms: f64,
+    save_at: Option<f64>,
+    /// Deadline set by the first unsaved change, which later changes cannot push back
+    save_by: Option<f64>,
+    last_error: Option<PersistError>,
+}
+
+impl<T> Persistence<T> {
+    pub(crate) fn new(config: PersistConfig) -> Self
+    where
+        T: Serialize,
+    {
+        let delay_ms = config.delay.as_secs_f64() * 1000.0;
+        let max_wait_ms = config.max_wait.as_secs_f64() * 1000.0;
+        Self {
+            save: Box::new(move |state| config.save(state)),
+            delay_ms,
+            max_wait_ms,
+            save_at: None,
+            save_by: None,
+            last_error: None,
+        }
+    }
+
+    /// Note that the state changed at `now`, pushing the next save back
+    ///
+    /// The save is never pushed past the max wait from the first unsaved change.
+    pub fn mark_changed(&mut self, now: f64) {
+        let save_by = *self.save_by.get_or_insert(now + self.max_wait_ms);
+        self.save_at = Some((now + self.delay_ms).min(save_by));
+    }
+
+    /// The time at which a pending save is due
+    pub fn next_deadline(&self) -> Option<f64> {
+        self.save_at
+    }
+
+    /// Save if a pending save is due at `now`
+    pub fn poll(&mut self, state: &T, now: f64) {
+        if self.save_at.is_some_and(|at| at <= now) {
+            self.flush(state);
+        }
+    }
+
+    /// Save right away if there are unsaved changes
+    pub fn flush(&mut self, state: &T) {
+        self.save_by = None;
+        if self.save_at.take().is_some() {
+            self.last_error = (self.save)(state).err();
+        }
+    }
+
+    /// The error from the most recent save, if it failed
+    pub fn last_error(&self) -> Option<&PersistError> {
+        self.last_error.as_ref()
+    }
+}
@@ -0,0 +1,205 @@
+//! Recording and replay of input sessions
+//!
+//! While recording, an app logs everything the host feeds into it: input events,
+//! renders with the dimensions in effect, polls and animation ticks. A
+//! [`Recording`] can be exported as JSON and replayed into a fresh app, natively
+//! or in the browser, to reproduce a session step by step.
+//!
+//! # JSON format
+//!
+//! A recording is an object with a format `version` (currently 1) and a list of
+//! `entries`. Each entry has a `kind` and the fields for that kind:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "entries": [
+//!     { "kind": "render", "width": 800, "height": 600 },
+//!     { "kind": "mouse", "event_type": "pointerdown", "x": 12.0, "y": 40.0,
+//!       "delta_x": 0.0, "delta_y": 0.0, "delta_z": 0.0, "delta_mode": "pixel",
+//!       "pointer_id": 1, "pointer_type": "mouse", "pressure": 0.5, "button": 0,
+//!       "buttons": 1, "modifiers": { "shift": false, "ctrl": false, "alt": false,
+//!       "meta": false }, "timestamp": 1520.5 },
+//!     { "kind": "key", "event_type": "keydown", "key": "a", "code": "KeyA",
+//!       "repeat": false, "modifiers": { ... }, "timestamp": 1600.0 },
+//!     { "kind": "blur" },
+//!     { "kind": "text", "event_type": "paste", "data": "hello" },
+//!     { "kind": "custom", "name": "file-loaded", "detail": "{}" },
+//!     { "kind": "poll", "now": 2020.5 },
+//!     { "kind": "tick", "dt": 16.7 }
+//!   ]
+//! }
+//! ```
+//!
+//! Times are in milliseconds. Results of async tasks are not recorded, so apps
+//! that spawn tasks only replay deterministically if the tasks do.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AppCore, CustomEvent, Dimensions, Event, KeyEvent, MouseEvent, TextEvent};
+
+/// Version of the JSON format written by [`Recording::to_json`]
+pub const FORMAT_VERSION: u32 = 1;
+
+/// One step of a recorded session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordedInput {
+    Mouse(MouseEvent),
+    Key(KeyEvent),
+    /// The window lost focus
+    Blur,
+    /// Keyboard focus moved from the app to another part of the page
+    FocusOut,
+    Custom(CustomEvent),
+    Text(TextEvent),
+    /// The app was rendered at these dimensions
+    Render(Dimensions),
+    /// The host polled for timers and other deadlines
+    Poll {
+        now: f64,
+    },
+    /// The host delivered an animation frame
+    Tick {
+        dt: f64,
+    },
+}
+
+impl From<Event> for RecordedInput {
+    fn from(event: Event) -> Self {
+        match event {
+            Event::Mouse(event) => RecordedInput::Mouse(event),
+            Event::Key(event) => RecordedInput::Key(event),
+            Event::Blur => RecordedInput::Blur,
+            Event::FocusOut => RecordedInput::FocusOut,
+            Event::Custom(event) => RecordedInput::Custom(event),
+            Event::Text(event) => RecordedInput::Text(event),
+        }
+    }
+}
+
+/// A recorded input session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recording {
+    /// Format version, see [`FORMAT_VERSION`]
+    pub version: u32,
+    /// Recorded steps, in the order they happened
+    pub entries: Vec<RecordedInput>,
+}
+
+impl Default for Recording {
+    fn default() -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl Recording {
+    /// Create an empty recording
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a step
+    pub fn push(&mut self, input: impl Into<RecordedInput>) {
+        self.entries.push(input.into());
+    }
+
+    /// Number of recorded steps
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Serialize to the documented JSON format
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("recordings always serialize")
+    }
+
+    /// Parse a recording from JSON
+    pub fn from_json(json: &str) -> Result<Self, ReplayError> {
+        let recording: Recording = serde_json::from_str(json)?;
+        if recording.version != FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedVersion(recording.version));
+        }
+        Ok(recording)
+    }
+
+    /// Feed every recorded step into `app`
+    pub fn replay<A: AppCore>(&self, app: &mut A) {
+        self.replay_until(app, self.entries.len());
+    }
+
+    /// Feed the recorded steps before `index` into `app`
+    ///
+    /// Returns the SVG of the last replayed render, if any.
+    pub fn replay_until<A: AppCore>(&self, app: &mut A, index: usize) -> Option<String> {
+        let mut svg = None;
+        for input in self.entries.iter().take(index) {
+            match input.clone() {
+                RecordedInput::Mouse(event) => {
+                    app.handle_event(Event::Mouse(event));
+                }
+                RecordedInput::Key(event) => {
+                    app.handle_event(Event::Key(event));
+                }
+                RecordedInput::Blur => {
+                    app.handle_event(Event::Blur);
+                }
+                RecordedInput::FocusOut => {
+                    app.handle_event(Event::FocusOut);
+                }
+                RecordedInput::Custom(event) => {
+                    app.handle_event(Event::Custom(event));
+                }
+                RecordedInput::Text(event) => {
+                    app.handle_event(Event::Text(event));
+                }
+                RecordedInput::Render(dimensions) => svg = Some(app.render(dimensions)),
+                RecordedInput::Poll { now } => {
+                    app.poll(now);
+                }
+                RecordedInput::Tick { dt } => {
+                    app.tick(dt);
+                }
+            }
+        }
+        svg
+    }
+}
+
+/// Error returned when a recording cannot be loaded
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The JSON did not match the recording format
+    Json(serde_json::Error),
+    /// The recording was written by an incompatible format version
+    UnsupportedVersion(u32),
+}
+
+impl std::fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Json(err) => write!(f, "invalid recording: {}", err),
+            ReplayError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported recording version {} (expected {})",
+                version, FORMAT_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<serde_json::Error> for ReplayError {
+    fn from(err: serde_json::Error) -> Self {
+        ReplayError::Json(err)
+    }
+}
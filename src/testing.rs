@@ -0,0 +1,423 @@
+//! Headless testing for Salt applications
+//!
+//! [`TestHarness`] drives an [`App`] the way the browser host does, without a
+//! browser: it renders at fixed dimensions, feeds simulated pointer and keyboard
+//! input through the normal event pipeline, and exposes the resulting view and
+//! SVG for assertions. Time is virtual, so timers, long presses and multi-click
+//! detection behave deterministically under `cargo test`.
+//!
+//! Rendered views can be checked against stored snapshots with [`assert_snapshot`].
+//! Snapshots live in `tests/snapshots` as pretty-printed SVG; set `SALT_BLESS=1`
+//! to write or update them instead of comparing.
+
+use std::path::PathBuf;
+
+use crate::ui::{Point, Shape, SvgElement, View};
+use crate::{
+    App, AppCore, Dimensions, Event, EventType, KeyEvent, Modifiers, MouseEvent, PointerType,
+    TextEvent,
+};
+
+/// Milliseconds of virtual time that pass between simulated events
+pub const EVENT_INTERVAL_MS: f64 = 16.0;
+
+/// Pointer id used for simulated mouse input
+const MOUSE_POINTER_ID: i32 = 1;
+
+/// Environment variable that writes snapshots instead of comparing them when set
+pub const BLESS_VAR: &str = "SALT_BLESS";
+
+/// Decimal places kept for numbers in snapshots
+pub const SNAPSHOT_PRECISION: usize = 2;
+
+/// Path of the snapshot file for `name`, under the crate's `tests/snapshots` directory
+pub fn snapshot_path(name: &str) -> PathBuf {
+    let root = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_else(|| ".".into());
+    PathBuf::from(root)
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.svg", name))
+}
+
+/// Compare `svg` with the stored snapshot `name`, panicking with a structural diff on mismatch
+///
+/// With `SALT_BLESS` set, the snapshot is written instead, creating it if needed.
+pub fn assert_snapshot(name: &str, svg: &SvgElement) {
+    let path = snapshot_path(name);
+    let actual = svg.to_pretty_string(SNAPSHOT_PRECISION);
+
+    let bless = std::env::var(BLESS_VAR).is_ok_and(|value| !value.is_empty() && value != "0");
+    if bless {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .unwrap_or_else(|err| panic!("cannot create {}: {}", dir.display(), err));
+        }
+        std::fs::write(&path, &actual)
+            .unwrap_or_else(|err| panic!("cannot write {}: {}", path.display(), err));
+        return;
+    }
+
+    let Ok(expected) = std::fs::read_to_string(&path) else {
+        panic!(
+            "snapshot `{}` does not exist at {}\nrun with {}=1 to create it",
+            name,
+            path.display(),
+            BLESS_VAR
+        );
+    };
+    if expected == actual {
+        return;
+    }
+
+    let changes = match SvgElement::parse(&expected) {
+        Ok(stored) => stored.diff(&svg.normalized(SNAPSHOT_PRECISION)),
+        Err(err) => vec![format!("stored snapshot is unreadable: {}", err)],
+    };
+    let changes = if changes.is_empty() {
+        // Same structure, so only formatting differs
+        vec!["formatting differs from the stored snapshot".to_string()]
+    } else {
+        changes
+    };
+    panic!(
+        "snapshot `{}` does not match {}\n  {}\nrun with {}=1 to accept the new output",
+        name,
+        path.display(),
+        changes.join("\n  "),
+        BLESS_VAR
+    );
+}
+
+/// Runs an [`App`] natively and simulates user input against it
+pub struct TestHarness<A: App> {
+    app: A,
+    dimensions: Dimensions,
+    now: f64,
+    svg: String,
+    pressed: bool,
+    modifiers: Modifiers,
+}
+
+impl<A: App> TestHarness<A> {
+    /// Create the app and render it at `dimensions`
+    pub fn new(dimensions: Dimensions) -> Self {
+        Self::with_app(<A as App>::new(), dimensions)
+    }
+
+    /// Wrap an existing app and render it at `dimensions`
+    pub fn with_app(app: A, dimensions: Dimensions) -> Self {
+        let mut harness = Self {
+            app,
+            dimensions,
+            now: 0.0,
+            svg: String::new(),
+            pressed: false,
+            modifiers: Modifiers::default(),
+        };
+        harness.render();
+        harness
+    }
+
+    /// The app under test
+    pub fn app(&mut self) -> &mut A {
+        &mut self.app
+    }
+
+    /// The app's state
+    pub fn state(&mut self) -> &mut A::State {
+        self.app.state().1
+    }
+
+    /// The view produced by the last render
+    pub fn view(&mut self) -> &View<A::State> {
+        &self.app.state().0.view
+    }
+
+    /// The shapes produced by the last render
+    pub fn shapes(&mut self) -> &[Shape<A::State>] {
+        &self.view().shapes
+    }
+
+    /// Find a shape from the last render by its ID
+    pub fn shape(&mut self, id: u64) -> Option<&Shape<A::State>> {
+        let view = self.view();
+        view.find_shape_by_id(id).map(|idx| &view.shapes[idx])
+    }
+
+    /// Find the topmost text shape from the last render showing exactly `text`
+    pub fn shape_with_text(&mut self, text: &str) -> Option<&Shape<A::State>> {
+        let view = self.view();
+        view.find_shape_by_text(text).map(|idx| &view.shapes[idx])
+    }
+
+    /// The SVG produced by the last render
+    pub fn svg(&self) -> &str {
+        &self.svg
+    }
+
+    /// The element tree of the last render, including overlays such as tooltips
+    pub fn svg_tree(&self) -> SvgElement {
+        SvgElement::parse(&self.svg).expect("rendered SVG is well formed")
+    }
+
+    /// The ID of the shape holding keyboard focus
+    pub fn focused(&mut self) -> Option<u64> {
+        self.app.state().0.focus.focused_shape_id
+    }
+
+    /// The text of the tooltip showing after the last render, if any
+    pub fn tooltip(&mut self) -> Option<&str> {
+        self.app.state().0.visible_tooltip()
+    }
+
+    /// Compare the last render with the stored snapshot `name`
+    ///
+    /// See [`assert_snapshot`].
+    pub fn assert_snapshot(&mut self, name: &str) -> &mut Self {
+        assert_snapshot(name, &self.svg_tree());
+        self
+    }
+
+    /// The dimensions the app is rendered at
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    /// The current virtual time in milliseconds
+    pub fn now(&self) -> f64 {
+        self.now
+    }
+
+    /// Render the app again, returning the SVG
+    pub fn render(&mut self) -> &str {
+        self.svg = self.app.render(self.dimensions);
+        &self.svg
+    }
+
+    /// Render the app at new dimensions
+    pub fn resize(&mut self, dimensions: Dimensions) -> &mut Self {
+        self.dimensions = dimensions;
+        self.render();
+        self
+    }
+
+    /// Hold modifier keys for the following pointer and key events
+    pub fn with_modifiers(&mut self, modifiers: Modifiers) -> &mut Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    /// Let `ms` milliseconds of virtual time pass, firing anything that comes due
+    pub fn advance(&mut self, ms: f64) -> &mut Self {
+        let target = self.now + ms;
+
+        // Visit each deadline on the way, so timers see the times they were due at
+        while let Some(deadline) = self.app.next_deadline().filter(|&d| d <= target) {
+            self.now = self.now.max(deadline);
+            self.app.poll(self.now);
+            // A deadline that polling leaves in place is a framework bug, not a test failure
+            if let Some(next) = self.app.next_deadline().filter(|&next| next <= deadline) {
+                panic!("deadline at {deadline}ms did not advance after polling (next is {next}ms)");
+            }
+        }
+        self.now = target;
+        self.app.poll(self.now);
+        self.render();
+        self
+    }
+
+    /// Deliver an animation frame `dt` milliseconds after the last one
+    ///
+    /// As in the browser, the app only ticks if it requested the frame.
+    pub fn frame(&mut self, dt: f64) -> &mut Self {
+        self.now += dt;
+        self.app.poll(self.now);
+        AppCore::tick(&mut self.app, dt);
+        self.render();
+        self
+    }
+
+    /// Deliver any event through the app's event pipeline, then re-render
+    pub fn send(&mut self, event: impl Into<Event>) -> &mut Self {
+        self.app.handle_event(event.into());
+        self.app.poll(self.now);
+        self.render();
+        self
+    }
+
+    /// Press the primary button at a point
+    pub fn pointer_down(&mut self, x: f32, y: f32) -> &mut Self {
+        self.pressed = true;
+        self.pointer_event(EventType::PointerDown, x, y)
+    }
+
+    /// Move the pointer to a point, dragging if the button is held
+    pub fn pointer_move(&mut self, x: f32, y: f32) -> &mut Self {
+        self.pointer_event(EventType::PointerMove, x, y)
+    }
+
+    /// Release the primary button at a point
+    pub fn pointer_up(&mut self, x: f32, y: f32) -> &mut Self {
+        self.pressed = false;
+        self.pointer_event(EventType::PointerUp, x, y)
+    }
+
+    /// Move the pointer over a point without pressing
+    pub fn hover(&mut self, x: f32, y: f32) -> &mut Self {
+        self.pointer_move(x, y)
+    }
+
+    /// Click at a point
+    pub fn click(&mut self, x: f32, y: f32) -> &mut Self {
+        self.pointer_down(x, y).pointer_up(x, y)
+    }
+
+    /// Click twice in quick succession at a point
+    pub fn double_click(&mut self, x: f32, y: f32) -> &mut Self {
+        self.click(x, y).click(x, y)
+    }
+
+    /// Press at `from`, move to `to` in `steps` equal moves, and release
+    pub fn drag(&mut self, from: Point, to: Point, steps: usize) -> &mut Self {
+        self.pointer_down(from.x, from.y);
+        let steps = steps.max(1);
+        for step in 1..=steps {
+            let t = step as f32 / steps as f32;
+            self.pointer_move(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t);
+        }
+        self.pointer_up(to.x, to.y)
+    }
+
+    /// Click the center of the shape with the given ID
+    ///
+    /// Panics if the last render has no such shape.
+    pub fn click_shape(&mut self, id: u64) -> &mut Self {
+        let point = self.shape_center(id);
+        self.click(point.x, point.y)
+    }
+
+    /// Click the center of the topmost text shape showing exactly `text`
+    ///
+    /// Panics if the last render has no such shape.
+    pub fn click_text(&mut self, text: &str) -> &mut Self {
+        let point = self
+            .shape_with_text(text)
+            .and_then(|shape| shape.center())
+            .unwrap_or_else(|| panic!("no text shape showing {:?}", text));
+        self.click(point.x, point.y)
+    }
+
+    /// Move the pointer over the center of the shape with the given ID
+    ///
+    /// Panics if the last render has no such shape.
+    pub fn hover_shape(&mut self, id: u64) -> &mut Self {
+        let point = self.shape_center(id);
+        self.hover(point.x, point.y)
+    }
+
+    /// Drag from the center of the shape with the given ID to `to`
+    ///
+    /// Panics if the last render has no such shape.
+    pub fn drag_shape(&mut self, id: u64, to: Point, steps: usize) -> &mut Self {
+        let from = self.shape_center(id);
+        self.drag(from, to, steps)
+    }
+
+    /// Press a key down
+    pub fn key_down(&mut self, key: &str) -> &mut Self {
+        self.key_event(EventType::KeyDown, key)
+    }
+
+    /// Release a key
+    pub fn key_up(&mut self, key: &str) -> &mut Self {
+        self.key_event(EventType::KeyUp, key)
+    }
+
+    /// Press and release a key
+    pub fn press_key(&mut self, key: &str) -> &mut Self {
+        self.key_down(key).key_up(key)
+    }
+
+    /// Press and release a key for each character of `text`
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        for c in text.chars() {
+            self.press_key(&c.to_string());
+        }
+        self
+    }
+
+    /// The text a copy would put on the clipboard, from the focused text field
+    pub fn copy(&mut self) -> Option<String> {
+        self.app.selected_text()
+    }
+
+    /// Cut the selection of the focused text field, returning the cut text
+    pub fn cut(&mut self) -> Option<String> {
+        let text = self.copy();
+        self.text_event(EventType::Cut, "");
+        text
+    }
+
+    /// Paste `text` into the focused text field
+    pub fn paste(&mut self, text: &str) -> &mut Self {
+        self.text_event(EventType::Paste, text)
+    }
+
+    /// Compose `text` through an input method, one character at a time, then commit it
+    pub fn compose(&mut self, text: &str) -> &mut Self {
+        self.text_event(EventType::CompositionStart, "");
+        let mut composed = String::new();
+        for c in text.chars() {
+            composed.push(c);
+            self.text_event(EventType::CompositionUpdate, &composed);
+        }
+        self.text_event(EventType::CompositionEnd, text)
+    }
+
+    /// Deliver a text input, clipboard or composition event
+    pub fn text_event(&mut self, event_type: EventType, data: &str) -> &mut Self {
+        self.now += EVENT_INTERVAL_MS;
+        self.send(TextEvent {
+            event_type,
+            data: data.to_string(),
+        })
+    }
+
+    /// Move keyboard focus from the app to another part of the page
+    pub fn focus_out(&mut self) -> &mut Self {
+        self.send(Event::FocusOut)
+    }
+
+    fn shape_center(&mut self, id: u64) -> Point {
+        self.shape(id)
+            .and_then(|shape| shape.center())
+            .unwrap_or_else(|| panic!("no shape with id {}", id))
+    }
+
+    fn pointer_event(&mut self, event_type: EventType, x: f32, y: f32) -> &mut Self {
+        self.now += EVENT_INTERVAL_MS;
+        let event = MouseEvent {
+            pointer_id: MOUSE_POINTER_ID,
+            pointer_type: PointerType::Mouse,
+            pressure: if self.pressed { 0.5 } else { 0.0 },
+            buttons: u16::from(self.pressed),
+            modifiers: self.modifiers,
+            timestamp: self.now,
+            ..MouseEvent::new(event_type, x as f64, y as f64)
+        };
+        self.send(event)
+    }
+
+    fn key_event(&mut self, event_type: EventType, key: &str) -> &mut Self {
+        self.now += EVENT_INTERVAL_MS;
+        let event = KeyEvent {
+            event_type,
+            key: key.to_string(),
+            code: String::new(),
+            repeat: false,
+            modifiers: self.modifiers,
+            timestamp: self.now,
+        };
+        self.send(event)
+    }
+}
@@ -0,0 +1,134 @@
+//! Accessibility metadata for Salt UI
+//!
+//! Shapes can declare a role, a label, a description and widget state. The
+//! renderer turns these into `role` and `aria-*` attributes and `<title>` and
+//! `<desc>` children, which screen readers announce.
+
+use std::fmt;
+
+use crate::ui::svg::SvgElement;
+
+/// ARIA role of a shape, telling assistive technology what kind of widget it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    Button,
+    Checkbox,
+    Switch,
+    Radio,
+    Slider,
+    /// A picture or icon, announced by its label
+    Img,
+    Link,
+    Heading,
+    Group,
+    List,
+    ListItem,
+    Tab,
+    Menu,
+    MenuItem,
+    TextBox,
+    /// Purely decorative, hidden from assistive technology
+    Presentation,
+}
+
+impl Role {
+    /// The value of the ARIA `role` attribute for this role
+    pub fn as_aria(&self) -> &'static str {
+        match self {
+            Role::Button => "button",
+            Role::Checkbox => "checkbox",
+            Role::Switch => "switch",
+            Role::Radio => "radio",
+            Role::Slider => "slider",
+            Role::Img => "img",
+            Role::Link => "link",
+            Role::Heading => "heading",
+            Role::Group => "group",
+            Role::List => "list",
+            Role::ListItem => "listitem",
+            Role::Tab => "tab",
+            Role::Menu => "menu",
+            Role::MenuItem => "menuitem",
+            Role::TextBox => "textbox",
+            Role::Presentation => "presentation",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_aria())
+    }
+}
+
+/// Current value of a slider or other range widget
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RangeValue {
+    pub now: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+/// What a shape tells assistive technology about itself
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Accessibility {
+    pub role: Option<Role>,
+    /// Accessible name, rendered as a `<title>` child
+    pub label: Option<String>,
+    /// Longer description, rendered as a `<desc>` child
+    pub description: Option<String>,
+    /// Whether a toggle button is pressed
+    pub pressed: Option<bool>,
+    /// Whether a checkbox, switch or radio button is checked
+    pub checked: Option<bool>,
+    /// Whether the widget is disabled
+    pub disabled: bool,
+    /// Value of a range widget such as a slider
+    pub value: Option<RangeValue>,
+}
+
+impl Accessibility {
+    /// Add the role, ARIA state and title and description children to `element`
+    pub(crate) fn apply(&self, mut element: SvgElement) -> SvgElement {
+        if let Some(role) = self.role {
+            element = element.attr("role", role);
+        }
+        if let Some(pressed) = self.pressed {
+            element = element.attr("aria-pressed", pressed);
+        }
+        if let Some(checked) = self.checked {
+            element = element.attr("aria-checked", checked);
+        }
+        if self.disabled {
+            element = element.attr("aria-disabled", true);
+        }
+        if let Some(value) = self.value {
+            element = element
+                .attr("aria-valuenow", value.now)
+                .attr("aria-valuemin", value.min)
+                .attr("aria-valuemax", value.max);
+        }
+
+        // SVG takes the accessible name and description from the first title and
+        // desc children, so they go before any other content
+        let mut described = Vec::new();
+        if let Some(label) = &self.label {
+            described.push(SvgElement::new("title").text(label.clone()));
+        }
+        if let Some(description) = &self.description {
+            described.push(SvgElement::new("desc").text(description.clone()));
+        }
+        if described.is_empty() {
+            return element;
+        }
+        // Text content is written before the children, so a text shape's text
+        // moves into a tspan that follows the title and desc
+        if let Some(text) = element.text.take() {
+            element
+                .children
+                .insert(0, SvgElement::new("tspan").text(text));
+        }
+        element.children.splice(0..0, described);
+        element
+    }
+}
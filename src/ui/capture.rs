@@ -0,0 +1,44 @@
+//! Pointer capture for Salt UI applications
+//!
+//! This module provides a shared handle for routing pointer events to one shape.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Shared record of the shape holding pointer capture
+///
+/// Cloning the handle shares the same capture, so a clone can be moved into
+/// shape callbacks to capture the pointer when a drag starts.
+#[derive(Clone, Debug, Default)]
+pub struct PointerCapture {
+    shape_id: Rc<Cell<Option<u64>>>,
+}
+
+impl PointerCapture {
+    /// Create a handle with no shape holding the capture
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route every pointer event to the given shape, regardless of hit testing
+    ///
+    /// Capture lasts until `release` is called or no pointer is pressed anymore.
+    pub fn capture(&self, shape_id: u64) {
+        self.shape_id.set(Some(shape_id));
+    }
+
+    /// Stop routing pointer events to the capturing shape
+    pub fn release(&self) {
+        self.shape_id.set(None);
+    }
+
+    /// Get the ID of the shape holding the capture, if any
+    pub fn captured_id(&self) -> Option<u64> {
+        self.shape_id.get()
+    }
+
+    /// Whether any shape holds the capture
+    pub fn is_captured(&self) -> bool {
+        self.shape_id.get().is_some()
+    }
+}
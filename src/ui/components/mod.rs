@@ -5,7 +5,9 @@
 mod path;
 mod rect;
 mod text;
+mod text_input;
 
 pub use path::{path, PathBuilder, PathCommand};
 pub use rect::{rect, RectBuilder};
 pub use text::{text, TextBuilder};
+pub use text_input::{text_input, TextInputBuilder};
@@ -20,6 +20,11 @@ impl RectBuilder {
     pub fn hit_test_shape(&self, x: f32, y: f32) -> bool {
         x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
     }
+
+    /// Bounding box as `(min_x, min_y, max_x, max_y)`
+    pub fn bounds(&self) -> (f32, f32, f32, f32) {
+        (self.x, self.y, self.x + self.width, self.y + self.height)
+    }
 }
 
 impl RectBuilder {
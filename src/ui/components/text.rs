@@ -18,6 +18,12 @@ pub struct TextBuilder {
 
 impl TextBuilder {
     pub fn hit_test_shape(&self, x: f32, y: f32) -> bool {
+        let (left, top, right, bottom) = self.bounds();
+        x >= left && x <= right && y >= top && y <= bottom
+    }
+
+    /// Estimated bounding box as `(min_x, min_y, max_x, max_y)`
+    pub fn bounds(&self) -> (f32, f32, f32, f32) {
         let text_width = self.text.len() as f32 * self.font_size * 0.6;
         let text_height = self.font_size * 1.2;
 
@@ -27,10 +33,7 @@ impl TextBuilder {
             _ => (self.x, self.x + text_width), // start or default
         };
 
-        let top = self.y - text_height;
-        let bottom = self.y;
-
-        x >= left && x <= right && y >= top && y <= bottom
+        (left, self.y - text_height, right, self.y)
     }
 
     pub fn x(mut self, x: f32) -> Self {
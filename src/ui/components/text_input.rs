@@ -0,0 +1,308 @@
+//! Text field component for Salt UI
+//!
+//! This module provides an editable text field for Salt applications. The field
+//! shows the value passed to it on each render; edits reach the application
+//! through the shape's `on_input` callback.
+//!
+//! Caret and click positions assume a fixed advance of 0.6em per character, which
+//! matches common monospace fonts, so fields default to a monospace font.
+
+use crate::ui::accessibility::Role;
+use crate::ui::cursor::Cursor;
+use crate::ui::gesture::Point;
+use crate::ui::svg::SvgElement;
+use crate::ui::text_edit::{line_col, position_at, TextEditState};
+use crate::ui::{color::Color, Shape, ShapeType};
+
+#[derive(Clone)]
+pub struct TextInputBuilder {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub value: String,
+    /// Hint shown while the value is empty
+    pub placeholder: String,
+    pub font_family: String,
+    pub font_size: f32,
+    pub text_color: Color,
+    pub placeholder_color: Color,
+    pub background: Color,
+    pub border: Color,
+    pub border_width: f32,
+    pub corner_radius: f32,
+    /// Space between the border and the text
+    pub padding: f32,
+    pub selection_color: Color,
+    pub caret_color: Color,
+    /// Whether Enter inserts line breaks
+    pub multiline: bool,
+}
+
+impl TextInputBuilder {
+    pub fn hit_test_shape(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
+    }
+
+    /// Bounding box as `(min_x, min_y, max_x, max_y)`
+    pub fn bounds(&self) -> (f32, f32, f32, f32) {
+        (self.x, self.y, self.x + self.width, self.y + self.height)
+    }
+
+    /// Estimated advance of one character
+    pub fn char_width(&self) -> f32 {
+        self.font_size * 0.6
+    }
+
+    /// Distance between the tops of consecutive lines
+    pub fn line_height(&self) -> f32 {
+        self.font_size * 1.2
+    }
+
+    /// Character position in the value closest to `point`
+    pub fn position_at_point(&self, point: Point) -> usize {
+        let line = ((point.y - self.y - self.padding) / self.line_height()).max(0.0) as usize;
+        let col = ((point.x - self.x - self.padding) / self.char_width())
+            .round()
+            .max(0.0) as usize;
+        position_at(&self.value, line, col)
+    }
+
+    /// Top left corner of the caret at character `position`
+    pub fn caret_point(&self, position: usize) -> Point {
+        let (line, col) = line_col(&self.value, position);
+        Point::new(
+            self.x + self.padding + col as f32 * self.char_width(),
+            self.y + self.padding + line as f32 * self.line_height(),
+        )
+    }
+
+    /// Build the SVG for the field, with the caret and selection of `edit` when it is focused
+    pub fn to_svg(&self, edit: Option<&TextEditState>) -> SvgElement {
+        let mut group = SvgElement::new("g").attr("class", "salt-text-input").child(
+            SvgElement::new("rect")
+                .attr("x", self.x)
+                .attr("y", self.y)
+                .attr("width", self.width)
+                .attr("height", self.height)
+                .attr("rx", self.corner_radius)
+                .attr("ry", self.corner_radius)
+                .attr("fill", format!("{:x}", self.background.to_rgba8()))
+                .attr("stroke", format!("{:x}", self.border.to_rgba8()))
+                .attr("stroke-width", self.border_width),
+        );
+
+        // Selections are highlighted line by line, behind the text
+        if let Some(edit) = edit.filter(|edit| edit.has_selection()) {
+            let (start, end) = edit.selection();
+            let (start_line, _) = line_col(&self.value, start);
+            let (end_line, _) = line_col(&self.value, end);
+            for line in start_line..=end_line {
+                let from = start.max(position_at(&self.value, line, 0));
+                let to = end.min(position_at(&self.value, line, usize::MAX));
+                let corner = self.caret_point(from);
+                // Selected line breaks show as a sliver, like in native fields
+                let width = ((to - from) as f32 * self.char_width()).max(self.char_width() / 2.0);
+                group = group.child(
+                    SvgElement::new("rect")
+                        .attr("x", corner.x)
+                        .attr("y", corner.y)
+                        .attr("width", width)
+                        .attr("height", self.line_height())
+                        .attr("fill", format!("{:x}", self.selection_color.to_rgba8())),
+                );
+            }
+        }
+
+        // Text being composed is shown at the caret, underlined, until it is committed
+        let composition = edit.and_then(|edit| Some((edit.caret, edit.composition.as_deref()?)));
+        let shown = match composition {
+            Some((caret, text)) => {
+                let mut shown: String = self.value.chars().take(caret).collect();
+                shown.push_str(text);
+                shown.extend(self.value.chars().skip(caret));
+                shown
+            }
+            None => self.value.clone(),
+        };
+
+        let (lines, color) = if shown.is_empty() {
+            (self.placeholder.as_str(), self.placeholder_color)
+        } else {
+            (shown.as_str(), self.text_color)
+        };
+        for (line, text) in lines.split('\n').enumerate() {
+            if text.is_empty() {
+                continue;
+            }
+            group = group.child(
+                SvgElement::new("text")
+                    .attr("x", self.x + self.padding)
+                    .attr(
+                        "y",
+                        self.y + self.padding + line as f32 * self.line_height() + self.font_size,
+                    )
+                    .attr("font-family", &self.font_family)
+                    .attr("font-size", self.font_size)
+                    .attr("fill", format!("{:x}", color.to_rgba8()))
+                    .attr("xml:space", "preserve")
+                    .text(text),
+            );
+        }
+
+        let Some(edit) = edit else {
+            return group;
+        };
+        let composed = composition.map_or(0, |(_, text)| text.chars().count());
+        if let Some((caret, _)) = composition.filter(|_| composed > 0) {
+            let start = self.caret_point(caret);
+            let baseline = start.y + self.line_height();
+            group = group.child(
+                SvgElement::new("line")
+                    .attr("x1", start.x)
+                    .attr("y1", baseline)
+                    .attr("x2", start.x + composed as f32 * self.char_width())
+                    .attr("y2", baseline)
+                    .attr("stroke", format!("{:x}", self.text_color.to_rgba8()))
+                    .attr("stroke-width", 1),
+            );
+        }
+
+        let caret = self.caret_point(edit.caret);
+        group.child(
+            SvgElement::new("rect")
+                .attr("class", "salt-caret")
+                .attr("x", caret.x + composed as f32 * self.char_width())
+                .attr("y", caret.y)
+                .attr("width", 1)
+                .attr("height", self.line_height())
+                .attr("fill", format!("{:x}", self.caret_color.to_rgba8())),
+        )
+    }
+}
+
+impl TextInputBuilder {
+    pub fn x(mut self, x: f32) -> Self {
+        self.x = x;
+        self
+    }
+
+    pub fn y(mut self, y: f32) -> Self {
+        self.y = y;
+        self
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Set the value shown in the field, normally taken from the application state
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = value.into();
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    pub fn font_family(mut self, font_family: impl Into<String>) -> Self {
+        self.font_family = font_family.into();
+        self
+    }
+
+    pub fn font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    pub fn text_color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self
+    }
+
+    pub fn placeholder_color(mut self, color: Color) -> Self {
+        self.placeholder_color = color;
+        self
+    }
+
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    pub fn border(mut self, color: Color) -> Self {
+        self.border = color;
+        self
+    }
+
+    pub fn border_width(mut self, width: f32) -> Self {
+        self.border_width = width;
+        self
+    }
+
+    pub fn corner_radius(mut self, radius: f32) -> Self {
+        self.corner_radius = radius;
+        self
+    }
+
+    pub fn padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn selection_color(mut self, color: Color) -> Self {
+        self.selection_color = color;
+        self
+    }
+
+    pub fn caret_color(mut self, color: Color) -> Self {
+        self.caret_color = color;
+        self
+    }
+
+    /// Allow several lines, with Enter inserting a line break
+    pub fn multiline(mut self, multiline: bool) -> Self {
+        self.multiline = multiline;
+        self
+    }
+
+    /// Finish the field as a focusable shape with a text cursor and the textbox role
+    pub fn finish<T>(self, id: u64) -> Shape<T> {
+        let mut shape = Shape::new(id, ShapeType::TextInput(self));
+        shape.focusable = true;
+        shape.cursor = Some(Cursor::Text);
+        shape.accessibility.role = Some(Role::TextBox);
+        shape
+    }
+}
+
+pub fn text_input() -> TextInputBuilder {
+    TextInputBuilder {
+        x: 0.0,
+        y: 0.0,
+        width: 200.0,
+        height: 30.0,
+        value: String::new(),
+        placeholder: String::new(),
+        font_family: "monospace".to_string(),
+        font_size: 14.0,
+        text_color: Color::BLACK,
+        placeholder_color: Color::new([0.3, 0.3, 0.3, 1.0]),
+        background: Color::WHITE,
+        border: Color::new([0.3, 0.3, 0.3, 1.0]),
+        border_width: 1.0,
+        corner_radius: 3.0,
+        padding: 6.0,
+        selection_color: Color::new([0.2, 0.45, 1.0, 0.35]),
+        caret_color: Color::BLACK,
+        multiline: false,
+    }
+}
@@ -2,18 +2,71 @@
 //!
 //! This module provides a context that encapsulates the state needed by Salt applications.
 
-use crate::{ui::view::View, Dimensions, DragState, HoverState};
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    persist::{PersistConfig, PersistError, Persistence},
+    recording::{RecordedInput, Recording},
+    ui::{
+        capture::PointerCapture,
+        components::TextInputBuilder,
+        focus::FocusRingStyle,
+        frame::FrameRequests,
+        gesture::GestureConfig,
+        history::History,
+        scheduler::{Scheduler, TimerHandle},
+        svg::SvgElement,
+        task::{Executor, Tasks},
+        text_edit::TextEditState,
+        tooltip::TooltipStyle,
+        view::{ShapeType, View},
+    },
+    ClickState, Dimensions, DragState, FocusState, HoverState, PinchState,
+};
 
 #[derive(Default, Clone, Debug)]
 pub struct GestureState {
-    pub drag: DragState,
+    /// Active drags, keyed by pointer ID
+    pub drags: HashMap<i32, DragState>,
     pub hover: HoverState,
+    pub click: ClickState,
+    pub pinch: PinchState,
+    /// Shape holding pointer capture
+    pub capture: PointerCapture,
+    pub config: GestureConfig,
 }
 
-pub struct AppCtx<T: ?Sized> {
+pub struct AppCtx<T> {
     pub view: View<T>,
     pub gestures: GestureState,
+    pub focus: FocusState,
     pub dimensions: Dimensions,
+    /// Animation frames requested from the host
+    pub frames: FrameRequests,
+    /// Timers waiting to run against the application state
+    pub scheduler: Scheduler<T>,
+    /// Spawned futures and their pending results
+    pub tasks: Tasks<T>,
+    /// Whether a callback prevented the default action of the event being handled
+    pub default_prevented: bool,
+    /// Session being recorded, if recording is on
+    pub recording: Option<Recording>,
+    /// Undo and redo stacks for the application state
+    pub history: History<T>,
+    /// Saving of the application state, once `persist` has been called
+    pub persistence: Option<Persistence<T>>,
+    /// Appearance of shape tooltips
+    pub tooltip_style: TooltipStyle,
+    /// Ring drawn around the shape focused by keyboard, or `None` to draw no ring
+    pub focus_ring: Option<FocusRingStyle>,
+    /// Document title announced for the rendered SVG
+    pub title: Option<String>,
+    /// Caret and selection of the text field being edited
+    pub text_edit: Option<TextEditState>,
 }
 
 impl<T> Default for AppCtx<T> {
@@ -21,10 +74,22 @@ impl<T> Default for AppCtx<T> {
         Self {
             view: View::new(),
             gestures: GestureState::default(),
+            focus: FocusState::default(),
             dimensions: Dimensions {
                 width: 0,
                 height: 0,
             },
+            frames: FrameRequests::new(),
+            scheduler: Scheduler::new(),
+            tasks: Tasks::new(),
+            default_prevented: false,
+            recording: None,
+            history: History::new(),
+            persistence: None,
+            tooltip_style: TooltipStyle::default(),
+            focus_ring: Some(FocusRingStyle::default()),
+            title: None,
+            text_edit: None,
         }
     }
 }
@@ -40,6 +105,85 @@ impl<T> AppCtx<T> {
         self.dimensions = dimensions;
     }
 
+    /// Set the document title, rendered as the `<title>` of the root SVG element
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = Some(title.into());
+    }
+
+    /// Build the SVG document for the current view, with its title and overlays
+    pub fn to_svg(&self) -> SvgElement {
+        let mut svg = self.view.to_svg(self.dimensions);
+        // Elements follow the shapes one to one, so the focused field can be
+        // redrawn with its caret and selection in place
+        if let Some((idx, input)) = self.focused_text_input() {
+            let element = input.to_svg(self.text_edit_state().as_ref());
+            svg.children[idx] = self.view.shapes[idx].accessibility.apply(element);
+        }
+        if let Some(title) = &self.title {
+            svg.children
+                .insert(0, SvgElement::new("title").text(title.clone()));
+        }
+        if let Some(ring) = self.focus_ring_overlay() {
+            svg = svg.child(ring);
+        }
+        if let Some(overlay) = self.tooltip_overlay() {
+            svg = svg.child(overlay);
+        }
+        svg
+    }
+
+    /// Index and builder of the focused shape, if it is a text field
+    fn focused_text_input(&self) -> Option<(usize, &TextInputBuilder)> {
+        let idx = self.view.find_shape_by_id(self.focus.focused_shape_id?)?;
+        match &self.view.shapes[idx].shape_type {
+            ShapeType::TextInput(input) => Some((idx, input)),
+            _ => None,
+        }
+    }
+
+    /// Caret and selection of the focused text field
+    ///
+    /// A field focused without a click, such as with Tab, has its caret at the end.
+    pub fn text_edit_state(&self) -> Option<TextEditState> {
+        let (idx, input) = self.focused_text_input()?;
+        let id = self.view.shapes[idx].id;
+        let mut edit = self
+            .text_edit
+            .clone()
+            .filter(|edit| edit.id == id)
+            .unwrap_or_else(|| TextEditState::new(id, input.value.chars().count()));
+        edit.clamp(&input.value);
+        Some(edit)
+    }
+
+    /// The text selected in the focused text field, for copying
+    pub fn selected_text(&self) -> Option<String> {
+        let (_, input) = self.focused_text_input()?;
+        self.text_edit_state()?.selected_text(&input.value)
+    }
+
+    /// Bounds of the caret in the focused text field, as `(min_x, min_y, max_x, max_y)`
+    pub fn caret_bounds(&self) -> Option<(f32, f32, f32, f32)> {
+        let (_, input) = self.focused_text_input()?;
+        let caret = input.caret_point(self.text_edit_state()?.caret);
+        Some((
+            caret.x,
+            caret.y,
+            caret.x + 1.0,
+            caret.y + input.line_height(),
+        ))
+    }
+
+    /// The ring around the focused shape, while focus is visible
+    pub fn focus_ring_overlay(&self) -> Option<SvgElement> {
+        if !self.focus.visible {
+            return None;
+        }
+        let style = self.focus_ring.as_ref()?;
+        let idx = self.view.find_shape_by_id(self.focus.focused_shape_id?)?;
+        Some(style.overlay(self.view.shapes[idx].bounds()?))
+    }
+
     /// Clear the view
     pub fn clear(&mut self) {
         self.view.clear();
@@ -47,10 +191,214 @@ impl<T> AppCtx<T> {
 
     /// Reset all interaction state
     pub fn reset_interaction(&mut self) {
-        self.gestures.drag.start_x = None;
-        self.gestures.drag.start_y = None;
-        self.gestures.drag.dragging_shape_id = None;
-        self.gestures.drag.mouse_down_id = None;
+        self.gestures.drags.clear();
+    }
+
+    /// The time, in milliseconds, at which a timer, long press, wheel pinch or tooltip becomes due
+    pub fn next_deadline(&self) -> Option<f64> {
+        let config = &self.gestures.config;
+        let wheel_pinch_end = self
+            .gestures
+            .pinch
+            .last_wheel_time
+            .map(|last| last + config.wheel_pinch_end_delay);
+
+        self.gestures
+            .drags
+            .values()
+            .filter(|drag| drag.long_press_pending)
+            .filter_map(|drag| drag.start_time)
+            .map(|start| start + config.long_press_delay)
+            .chain(wheel_pinch_end)
+            .chain(self.tooltip_deadline())
+            .chain(self.scheduler.next_deadline())
+            .chain(
+                self.persistence
+                    .as_ref()
+                    .and_then(|persistence| persistence.next_deadline()),
+            )
+            .reduce(f64::min)
+    }
+
+    /// The tooltip text of the hovered shape, or of its nearest ancestor that has one
+    fn hovered_tooltip(&self) -> Option<&str> {
+        let idx = self
+            .view
+            .find_shape_by_id(self.gestures.hover.hover_shape_id?)?;
+        self.view.tooltip_for(idx)
+    }
+
+    /// The time at which the hovered shape's tooltip is due to show
+    pub fn tooltip_deadline(&self) -> Option<f64> {
+        let since = self.gestures.hover.since?;
+        self.hovered_tooltip()?;
+        Some(since + self.gestures.config.tooltip_delay)
+    }
+
+    /// The text of the tooltip currently showing
+    pub fn visible_tooltip(&self) -> Option<&str> {
+        if !self.gestures.hover.tooltip_visible {
+            return None;
+        }
+        self.hovered_tooltip()
+    }
+
+    /// The overlay layer drawing the visible tooltip above every shape
+    pub fn tooltip_overlay(&self) -> Option<SvgElement> {
+        let text = self.visible_tooltip()?;
+        let anchor = self.gestures.hover.point?;
+        Some(self.tooltip_style.overlay(text, anchor, self.dimensions))
+    }
+
+    /// Run `callback` against the state once after `delay`
+    pub fn set_timeout(&self, delay: Duration, callback: impl Fn(&mut T) + 'static) -> TimerHandle {
+        self.scheduler.set_timeout(delay, callback)
+    }
+
+    /// Run `callback` against the state every `period`
+    pub fn set_interval(
+        &self,
+        period: Duration,
+        callback: impl Fn(&mut T) + 'static,
+    ) -> TimerHandle {
+        self.scheduler.set_interval(period, callback)
+    }
+
+    /// Cancel a timer, returning whether it was still pending
+    pub fn cancel_timer(&self, handle: TimerHandle) -> bool {
+        self.scheduler.cancel(handle)
+    }
+
+    /// Get a shared handle to the scheduler, for use inside shape callbacks
+    pub fn scheduler(&self) -> Scheduler<T> {
+        self.scheduler.clone()
+    }
+
+    /// Run `future` and hand its output to `on_complete` along with the state
+    ///
+    /// The app re-renders once the completion handler has run.
+    pub fn spawn<F>(&self, future: F, on_complete: impl FnOnce(&mut T, F::Output) + 'static)
+    where
+        F: Future + 'static,
+        T: 'static,
+    {
+        self.tasks.spawn(future, on_complete)
+    }
+
+    /// Replace the executor that runs spawned futures
+    pub fn set_executor(&self, executor: impl Executor + 'static) {
+        self.tasks.set_executor(executor)
+    }
+
+    /// Get a shared handle for spawning tasks, for use inside shape callbacks
+    pub fn tasks(&self) -> Tasks<T> {
+        self.tasks.clone()
+    }
+
+    /// Get a shared handle to the undo history, for use inside shape callbacks
+    pub fn history(&self) -> History<T> {
+        self.history.clone()
+    }
+
+    /// Restore `state` from storage, then keep saving it after it changes
+    ///
+    /// Returns whether a stored state was restored. If the stored state cannot be
+    /// restored, `state` is left as it was and the error is returned; persistence
+    /// is enabled either way, so the next save replaces the unreadable value.
+    pub fn persist(&mut self, state: &mut T, config: PersistConfig) -> Result<bool, PersistError>
+    where
+        T: Serialize + DeserializeOwned + 'static,
+    {
+        let restored = config.load::<T>();
+        self.persistence = Some(Persistence::new(config));
+
+        let Some(stored) = restored? else {
+            return Ok(false);
+        };
+        *state = stored;
+        Ok(true)
+    }
+
+    /// Note that the state changed, scheduling a save if persistence is enabled
+    pub fn state_changed(&mut self) {
+        let now = self.scheduler.now();
+        if let Some(persistence) = &mut self.persistence {
+            persistence.mark_changed(now);
+        }
+    }
+
+    /// Start recording input into a new session, discarding any session in progress
+    ///
+    /// The session opens with a render at the current dimensions, so a replay
+    /// hit-tests the recorded events against a built view.
+    pub fn start_recording(&mut self) {
+        let mut recording = Recording::new();
+        recording.push(RecordedInput::Render(self.dimensions));
+        self.recording = Some(recording);
+    }
+
+    /// Stop recording and return the session
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.recording.take()
+    }
+
+    /// Append a step to the session in progress, if any
+    pub fn record(&mut self, input: impl Into<RecordedInput>) {
+        if let Some(recording) = &mut self.recording {
+            recording.push(input);
+        }
+    }
+
+    /// Route every pointer event to the given shape, regardless of hit testing
+    ///
+    /// Capture lasts until `release_pointer` is called or no pointer is pressed anymore.
+    pub fn capture_pointer(&self, shape_id: u64) {
+        self.gestures.capture.capture(shape_id);
+    }
+
+    /// Stop routing pointer events to the capturing shape
+    pub fn release_pointer(&self) {
+        self.gestures.capture.release();
+    }
+
+    /// Get the ID of the shape holding pointer capture, if any
+    pub fn captured_id(&self) -> Option<u64> {
+        self.gestures.capture.captured_id()
+    }
+
+    /// Get a shared handle to pointer capture, for use inside shape callbacks
+    pub fn pointer_capture(&self) -> PointerCapture {
+        self.gestures.capture.clone()
+    }
+
+    /// Ask the host for a single animation frame, which calls `tick` and re-renders
+    pub fn request_animation_frame(&self) {
+        self.frames.request_animation_frame();
+    }
+
+    /// Ask the host for animation frames on every display refresh until turned off
+    pub fn set_continuous_animation(&self, continuous: bool) {
+        self.frames.set_continuous(continuous);
+    }
+
+    /// Get a shared handle to the frame requests, for use inside shape callbacks
+    pub fn frames(&self) -> FrameRequests {
+        self.frames.clone()
+    }
+
+    /// Get the ID of the focused shape, if any
+    pub fn focused_id(&self) -> Option<u64> {
+        self.focus.focused_shape_id
+    }
+
+    /// Move keyboard focus to the shape with the given ID
+    pub fn set_focus(&mut self, id: u64) {
+        self.focus.focused_shape_id = Some(id);
+    }
+
+    /// Remove keyboard focus from any shape
+    pub fn clear_focus(&mut self) {
+        self.focus.focused_shape_id = None;
     }
 
     /// Get the current dimensions
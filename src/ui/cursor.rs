@@ -0,0 +1,58 @@
+//! Cursor styles for Salt UI
+//!
+//! Shapes can set the cursor shown while the pointer is over them. The host
+//! applies the cursor reported by the app to its container.
+
+use std::fmt;
+
+/// Pointer cursor shown over a shape, mirroring the CSS `cursor` values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Cursor {
+    #[default]
+    Default,
+    Pointer,
+    Grab,
+    Grabbing,
+    Crosshair,
+    Text,
+    Move,
+    NotAllowed,
+    Wait,
+    Help,
+    /// Vertical resize (north-south)
+    ResizeNS,
+    /// Horizontal resize (east-west)
+    ResizeEW,
+    /// Diagonal resize (northeast-southwest)
+    ResizeNESW,
+    /// Diagonal resize (northwest-southeast)
+    ResizeNWSE,
+}
+
+impl Cursor {
+    /// The CSS `cursor` value for this cursor
+    pub fn as_css(&self) -> &'static str {
+        match self {
+            Cursor::Default => "default",
+            Cursor::Pointer => "pointer",
+            Cursor::Grab => "grab",
+            Cursor::Grabbing => "grabbing",
+            Cursor::Crosshair => "crosshair",
+            Cursor::Text => "text",
+            Cursor::Move => "move",
+            Cursor::NotAllowed => "not-allowed",
+            Cursor::Wait => "wait",
+            Cursor::Help => "help",
+            Cursor::ResizeNS => "ns-resize",
+            Cursor::ResizeEW => "ew-resize",
+            Cursor::ResizeNESW => "nesw-resize",
+            Cursor::ResizeNWSE => "nwse-resize",
+        }
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_css())
+    }
+}
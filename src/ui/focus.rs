@@ -0,0 +1,50 @@
+//! Keyboard focus rings for Salt UI
+//!
+//! When focus moves with the keyboard, the focused shape is outlined by a ring
+//! drawn above the view. Pointer presses move focus without showing the ring,
+//! like the CSS `:focus-visible` rule.
+
+use crate::ui::color::Color;
+use crate::ui::svg::SvgElement;
+
+/// Appearance of the ring drawn around the focused shape
+#[derive(Debug, Clone)]
+pub struct FocusRingStyle {
+    pub color: Color,
+    /// Stroke width of the ring
+    pub width: f32,
+    /// Gap between the shape's bounds and the ring
+    pub offset: f32,
+    pub corner_radius: f32,
+}
+
+impl Default for FocusRingStyle {
+    fn default() -> Self {
+        Self {
+            color: Color::new([0.0, 0.11, 1.0, 1.0]),
+            width: 2.0,
+            offset: 2.0,
+            corner_radius: 3.0,
+        }
+    }
+}
+
+impl FocusRingStyle {
+    /// Build the ring around bounds given as `(min_x, min_y, max_x, max_y)`
+    pub fn overlay(&self, bounds: (f32, f32, f32, f32)) -> SvgElement {
+        let (min_x, min_y, max_x, max_y) = bounds;
+        let inset = self.offset + self.width / 2.0;
+        SvgElement::new("rect")
+            .attr("class", "salt-focus-ring")
+            .attr("pointer-events", "none")
+            .attr("x", min_x - inset)
+            .attr("y", min_y - inset)
+            .attr("width", max_x - min_x + inset * 2.0)
+            .attr("height", max_y - min_y + inset * 2.0)
+            .attr("rx", self.corner_radius)
+            .attr("ry", self.corner_radius)
+            .attr("fill", "none")
+            .attr("stroke", format!("{:x}", self.color.to_rgba8()))
+            .attr("stroke-width", self.width)
+    }
+}
@@ -0,0 +1,49 @@
+//! Animation frame requests for Salt UI applications
+//!
+//! This module provides a shared handle for asking the host to drive animation frames.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Shared record of the animation frames an application wants
+///
+/// Cloning the handle shares the same requests, so a clone can be moved into
+/// shape callbacks to start or stop animations from there.
+#[derive(Clone, Debug, Default)]
+pub struct FrameRequests {
+    next_frame: Rc<Cell<bool>>,
+    continuous: Rc<Cell<bool>>,
+}
+
+impl FrameRequests {
+    /// Create a handle with no frames requested
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask for a single frame, after which ticking stops unless requested again
+    pub fn request_animation_frame(&self) {
+        self.next_frame.set(true);
+    }
+
+    /// Ask for a frame on every display refresh until turned off
+    pub fn set_continuous(&self, continuous: bool) {
+        self.continuous.set(continuous);
+    }
+
+    /// Whether frames are requested on every display refresh
+    pub fn is_continuous(&self) -> bool {
+        self.continuous.get()
+    }
+
+    /// Whether any frame is requested
+    pub fn wants_frame(&self) -> bool {
+        self.next_frame.get() || self.continuous.get()
+    }
+
+    /// Consume the request for the upcoming frame, returning whether one was pending
+    pub fn take_frame(&self) -> bool {
+        let requested = self.next_frame.replace(false);
+        requested || self.continuous.get()
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! This module provides types and utilities for handling gestures in Salt applications.
 
+use crate::{DeltaMode, Modifiers, MouseEvent, PointerType};
+
 /// Represents the phase of a drag interaction
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DragPhase {
@@ -11,6 +13,8 @@ pub enum DragPhase {
     Move,
     /// Drag has ended (contact released)
     End,
+    /// Drag was aborted before it could end, and its effects should be rolled back
+    Cancel,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -40,6 +44,208 @@ impl Point {
     }
 }
 
+/// Pointer and keyboard details for the event that triggered a gesture callback
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GestureInfo {
+    /// Modifier keys held during the event
+    pub modifiers: Modifiers,
+    /// Button that changed state, following the DOM `button` numbering
+    pub button: i16,
+    /// Bitmask of buttons currently pressed, following the DOM `buttons` numbering
+    pub buttons: u16,
+    /// Identifier of the pointer that produced the event
+    pub pointer_id: i32,
+    /// Kind of device that produced the event
+    pub pointer_type: PointerType,
+    /// Normalized pressure of the pointer in the range 0 to 1
+    pub pressure: f32,
+    /// Position in a sequence of rapid clicks: 1 for a single click, 2 for a double click
+    pub click_count: u32,
+}
+
+impl From<&MouseEvent> for GestureInfo {
+    fn from(event: &MouseEvent) -> Self {
+        Self {
+            modifiers: event.modifiers,
+            button: event.button,
+            buttons: event.buttons,
+            pointer_id: event.pointer_id,
+            pointer_type: event.pointer_type,
+            pressure: event.pressure,
+            click_count: 0,
+        }
+    }
+}
+
+/// Phase of event propagation in which a callback runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventPhase {
+    /// Travelling from the root shape down towards the target
+    Capture,
+    /// At the shape the event was aimed at
+    Target,
+    /// Travelling from the target back up to the root shape
+    Bubble,
+}
+
+/// Event details and propagation control passed to click callbacks
+///
+/// Dereferences to the [`GestureInfo`] of the event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EventCtx {
+    /// Pointer and keyboard details of the event
+    pub info: GestureInfo,
+    /// Current propagation phase
+    pub phase: EventPhase,
+    /// ID of the shape the event was aimed at
+    pub target_id: u64,
+    /// ID of the shape whose callback is running
+    pub current_id: u64,
+    propagation_stopped: bool,
+    default_prevented: bool,
+}
+
+impl EventCtx {
+    /// Create an event context aimed at the given shape
+    pub fn new(info: GestureInfo, target_id: u64) -> Self {
+        Self {
+            info,
+            phase: EventPhase::Target,
+            target_id,
+            current_id: target_id,
+            propagation_stopped: false,
+            default_prevented: false,
+        }
+    }
+
+    /// Move the event to the next shape along its propagation path
+    pub(crate) fn enter(&mut self, phase: EventPhase, current_id: u64) {
+        self.phase = phase;
+        self.current_id = current_id;
+    }
+
+    /// Prevent the event from reaching any further shapes
+    pub fn stop_propagation(&mut self) {
+        self.propagation_stopped = true;
+    }
+
+    /// Whether a callback has stopped propagation
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation_stopped
+    }
+
+    /// Ask the host page to suppress the browser's default action for the event
+    pub fn prevent_default(&mut self) {
+        self.default_prevented = true;
+    }
+
+    /// Whether a callback has prevented the default action
+    pub fn is_default_prevented(&self) -> bool {
+        self.default_prevented
+    }
+}
+
+impl std::ops::Deref for EventCtx {
+    type Target = GestureInfo;
+
+    fn deref(&self) -> &GestureInfo {
+        &self.info
+    }
+}
+
+/// Time and distance limits used to recognize gestures
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureConfig {
+    /// Maximum time between clicks, in milliseconds, for them to form a double or triple click
+    pub multi_click_time: f64,
+    /// Maximum distance between clicks for them to form a double or triple click
+    pub multi_click_distance: f32,
+    /// Distance a pointer must move while pressed before it counts as a drag instead of a click
+    pub drag_slop: f32,
+    /// Time a pointer must be held still, in milliseconds, to trigger a long press
+    pub long_press_delay: f64,
+    /// Maximum distance a pointer may move and still trigger a long press
+    pub long_press_distance: f32,
+    /// Time without ctrl+wheel events, in milliseconds, after which a wheel pinch ends
+    pub wheel_pinch_end_delay: f64,
+    /// How strongly each pixel of ctrl+wheel delta scales a wheel pinch
+    pub wheel_zoom_speed: f32,
+    /// Time the pointer must rest on a shape, in milliseconds, before its tooltip shows
+    pub tooltip_delay: f64,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            multi_click_time: 500.0,
+            multi_click_distance: 5.0,
+            drag_slop: 4.0,
+            long_press_delay: 500.0,
+            long_press_distance: 10.0,
+            wheel_pinch_end_delay: 150.0,
+            wheel_zoom_speed: 0.01,
+            tooltip_delay: 600.0,
+        }
+    }
+}
+
+/// Scale, rotation and centroid of a pinch gesture, relative to where it started
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PinchGesture {
+    /// Ratio of the current pointer distance to the starting distance
+    pub scale: f32,
+    /// Rotation since the pinch started, in radians
+    pub rotation: f32,
+    /// Midpoint between the pointers
+    pub centroid: Point,
+}
+
+/// Wheel or trackpad scroll input delivered to a shape
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollEvent {
+    /// Pointer position when the scroll happened
+    pub point: Point,
+    /// Horizontal scroll amount
+    pub delta_x: f32,
+    /// Vertical scroll amount
+    pub delta_y: f32,
+    /// Depth scroll amount
+    pub delta_z: f32,
+    /// Unit of the deltas
+    pub delta_mode: DeltaMode,
+    propagation_stopped: bool,
+}
+
+impl ScrollEvent {
+    /// Create a new scroll event
+    pub fn new(
+        point: Point,
+        delta_x: f32,
+        delta_y: f32,
+        delta_z: f32,
+        delta_mode: DeltaMode,
+    ) -> Self {
+        Self {
+            point,
+            delta_x,
+            delta_y,
+            delta_z,
+            delta_mode,
+            propagation_stopped: false,
+        }
+    }
+
+    /// Prevent the event from reaching shapes underneath this one
+    pub fn stop_propagation(&mut self) {
+        self.propagation_stopped = true;
+    }
+
+    /// Whether a handler has stopped propagation
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation_stopped
+    }
+}
+
 /// Gesture types that can be handled by UI components
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GestureType {
@@ -49,19 +255,46 @@ pub enum GestureType {
     Drag,
     /// A hover (pointer moving over an element without pressing)
     Hover,
+    /// A scroll (mouse wheel or trackpad swipe)
+    Scroll,
+    /// Two clicks in quick succession at the same place
+    DoubleClick,
+    /// A press held in place for a while without moving
+    LongPress,
+    /// Two pointers moving apart, together or around each other
+    Pinch,
 }
 
 /// Type definitions for gesture callbacks
 pub mod callbacks {
-    use super::{DragPhase, Point};
+    use super::{DragPhase, EventCtx, GestureInfo, PinchGesture, Point, ScrollEvent};
+    use crate::KeyEvent;
     use std::rc::Rc;
 
     /// Callback type for click/tap gestures
-    pub type OnClick<T> = Option<Rc<dyn Fn(&mut T)>>;
+    pub type OnClick<T> = Option<Rc<dyn Fn(&mut T, &mut EventCtx)>>;
+
+    /// Callback type for double click gestures
+    pub type OnDoubleClick<T> = Option<Rc<dyn Fn(&mut T)>>;
+
+    /// Callback type for long press gestures
+    pub type OnLongPress<T> = Option<Rc<dyn Fn(&mut T, Point)>>;
 
     /// Callback type for hover gestures
-    pub type OnHover<T> = Option<Rc<dyn Fn(&mut T, bool, Point)>>;
+    pub type OnHover<T> = Option<Rc<dyn Fn(&mut T, bool, Point, &GestureInfo)>>;
 
     /// Callback type for drag gestures
-    pub type OnDrag<T> = Option<Rc<dyn Fn(&mut T, DragPhase, Point, Point)>>;
+    pub type OnDrag<T> = Option<Rc<dyn Fn(&mut T, DragPhase, Point, Point, &GestureInfo)>>;
+
+    /// Callback type for pinch and rotate gestures
+    pub type OnPinch<T> = Option<Rc<dyn Fn(&mut T, DragPhase, PinchGesture)>>;
+
+    /// Callback type for scroll gestures
+    pub type OnScroll<T> = Option<Rc<dyn Fn(&mut T, &mut ScrollEvent)>>;
+
+    /// Callback type for keyboard events, returning true if the key was handled
+    pub type OnKey<T> = Option<Rc<dyn Fn(&mut T, &KeyEvent) -> bool>>;
+
+    /// Callback type for edits to a text field, receiving the new value
+    pub type OnInput<T> = Option<Rc<dyn Fn(&mut T, String)>>;
 }
@@ -0,0 +1,297 @@
+//! Undo and redo for Salt UI applications
+//!
+//! Changes are recorded either as snapshots of the state taken before a change,
+//! or as commands that know how to apply and revert themselves. Changes recorded
+//! while handling one event form a single undo step, and a drag or pinch from
+//! start to end is grouped into one step as well.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Default number of undo steps kept
+pub const DEFAULT_STEP_LIMIT: usize = 100;
+
+/// Summary of the history, passed to change listeners
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryStatus {
+    /// Number of steps that can be undone
+    pub undo_steps: usize,
+    /// Number of steps that can be redone
+    pub redo_steps: usize,
+}
+
+impl HistoryStatus {
+    /// Whether there is a step to undo
+    pub fn can_undo(&self) -> bool {
+        self.undo_steps > 0
+    }
+
+    /// Whether there is a step to redo
+    pub fn can_redo(&self) -> bool {
+        self.redo_steps > 0
+    }
+}
+
+type Action<T> = Rc<dyn Fn(&mut T)>;
+
+enum Change<T> {
+    /// The state on the other side of the change, swapped in on undo and redo
+    Snapshot(T),
+    Command {
+        apply: Action<T>,
+        revert: Action<T>,
+    },
+}
+
+type Step<T> = Vec<Change<T>>;
+
+struct HistoryInner<T> {
+    undo: Vec<Step<T>>,
+    redo: Vec<Step<T>>,
+    pending: Step<T>,
+    depth: usize,
+    gesture_open: bool,
+    step_limit: usize,
+    listeners: Vec<Rc<dyn Fn(HistoryStatus)>>,
+}
+
+/// Undo and redo stacks for an application's state
+///
+/// Cloning the history shares the same stacks, so a clone can be moved into
+/// shape callbacks to record changes from there.
+pub struct History<T> {
+    inner: Rc<RefCell<HistoryInner<T>>>,
+}
+
+impl<T> Clone for History<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(HistoryInner {
+                undo: Vec::new(),
+                redo: Vec::new(),
+                pending: Vec::new(),
+                depth: 0,
+                gesture_open: false,
+                step_limit: DEFAULT_STEP_LIMIT,
+                listeners: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl<T> History<T> {
+    /// Create an empty history keeping up to [`DEFAULT_STEP_LIMIT`] steps
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep at most `limit` undo steps, dropping the oldest ones
+    ///
+    /// The limit counts steps, not bytes: a step holding a large snapshot
+    /// counts the same as one holding a small command.
+    pub fn set_step_limit(&self, limit: usize) {
+        let mut inner = self.inner.borrow_mut();
+        inner.step_limit = limit;
+        inner.trim();
+    }
+
+    /// Record the state as it is before a change
+    ///
+    /// Call this before mutating the state. Within a step only the first
+    /// snapshot is kept, so the whole step undoes back to it.
+    pub fn checkpoint(&self, state: &T)
+    where
+        T: Clone,
+    {
+        let has_snapshot = {
+            let inner = self.inner.borrow();
+            inner
+                .pending
+                .iter()
+                .any(|change| matches!(change, Change::Snapshot(_)))
+        };
+        if !has_snapshot {
+            self.record(Change::Snapshot(state.clone()));
+        }
+    }
+
+    /// Apply a command to the state and record it with its inverse
+    pub fn execute(
+        &self,
+        state: &mut T,
+        apply: impl Fn(&mut T) + 'static,
+        revert: impl Fn(&mut T) + 'static,
+    ) {
+        apply(state);
+        self.record(Change::Command {
+            apply: Rc::new(apply),
+            revert: Rc::new(revert),
+        });
+    }
+
+    /// Start grouping changes into one step, until the matching `end_group`
+    pub fn begin_group(&self) {
+        self.inner.borrow_mut().depth += 1;
+    }
+
+    /// Close a group opened with `begin_group`, committing the step when the outermost group ends
+    pub fn end_group(&self) {
+        let committed = {
+            let mut inner = self.inner.borrow_mut();
+            inner.depth = inner.depth.saturating_sub(1);
+            inner.depth == 0 && inner.commit()
+        };
+        if committed {
+            self.notify();
+        }
+    }
+
+    /// Keep the current step open while a drag or pinch continues
+    pub(crate) fn hold_for_gesture(&self, active: bool) {
+        let release = {
+            let mut inner = self.inner.borrow_mut();
+            if active && !inner.gesture_open {
+                inner.gesture_open = true;
+                inner.depth += 1;
+            }
+            let release = !active && inner.gesture_open;
+            if release {
+                inner.gesture_open = false;
+            }
+            release
+        };
+        if release {
+            self.end_group();
+        }
+    }
+
+    /// Undo the last step, returning whether there was one
+    ///
+    /// Changes recorded so far in a step that is still open, such as a drag in
+    /// progress, are committed as their own step first and undone. The open
+    /// groups stay open, and changes after the undo form a new step.
+    pub fn undo(&self, state: &mut T) -> bool {
+        self.commit_pending();
+        let Some(mut step) = self.inner.borrow_mut().undo.pop() else {
+            return false;
+        };
+        for change in step.iter_mut().rev() {
+            match change {
+                Change::Snapshot(other) => std::mem::swap(state, other),
+                Change::Command { revert, .. } => revert(state),
+            }
+        }
+        self.inner.borrow_mut().redo.push(step);
+        self.notify();
+        true
+    }
+
+    /// Redo the last undone step, returning whether there was one
+    pub fn redo(&self, state: &mut T) -> bool {
+        self.commit_pending();
+        let Some(mut step) = self.inner.borrow_mut().redo.pop() else {
+            return false;
+        };
+        for change in step.iter_mut() {
+            match change {
+                Change::Snapshot(other) => std::mem::swap(state, other),
+                Change::Command { apply, .. } => apply(state),
+            }
+        }
+        self.inner.borrow_mut().undo.push(step);
+        self.notify();
+        true
+    }
+
+    /// Whether there is a step to undo
+    pub fn can_undo(&self) -> bool {
+        self.status().can_undo()
+    }
+
+    /// Whether there is a step to redo
+    pub fn can_redo(&self) -> bool {
+        self.status().can_redo()
+    }
+
+    /// Current number of undo and redo steps
+    pub fn status(&self) -> HistoryStatus {
+        let inner = self.inner.borrow();
+        HistoryStatus {
+            undo_steps: inner.undo.len() + usize::from(!inner.pending.is_empty()),
+            redo_steps: inner.redo.len(),
+        }
+    }
+
+    /// Forget every recorded step
+    pub fn clear(&self) {
+        {
+            let mut inner = self.inner.borrow_mut();
+            inner.undo.clear();
+            inner.redo.clear();
+            inner.pending.clear();
+        }
+        self.notify();
+    }
+
+    /// Call `listener` whenever steps are committed, undone, redone or cleared
+    pub fn on_change(&self, listener: impl Fn(HistoryStatus) + 'static) {
+        self.inner.borrow_mut().listeners.push(Rc::new(listener));
+    }
+
+    fn record(&self, change: Change<T>) {
+        let committed = {
+            let mut inner = self.inner.borrow_mut();
+            inner.pending.push(change);
+            inner.depth == 0 && inner.commit()
+        };
+        if committed {
+            self.notify();
+        }
+    }
+
+    /// Commit the changes of an open step without closing its groups
+    fn commit_pending(&self) {
+        let committed = self.inner.borrow_mut().commit();
+        if committed {
+            self.notify();
+        }
+    }
+
+    fn notify(&self) {
+        // Listeners may use the history, so never hold the borrow while calling them
+        let listeners = self.inner.borrow().listeners.clone();
+        let status = self.status();
+        for listener in listeners {
+            listener(status);
+        }
+    }
+}
+
+impl<T> HistoryInner<T> {
+    /// Move the pending changes onto the undo stack, returning whether there were any
+    fn commit(&mut self) -> bool {
+        if self.pending.is_empty() {
+            return false;
+        }
+        let step = std::mem::take(&mut self.pending);
+        self.undo.push(step);
+        self.redo.clear();
+        self.trim();
+        true
+    }
+
+    fn trim(&mut self) {
+        if self.undo.len() > self.step_limit {
+            let excess = self.undo.len() - self.step_limit;
+            self.undo.drain(..excess);
+        }
+    }
+}
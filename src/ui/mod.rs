@@ -2,14 +2,38 @@
 //!
 //! This module provides UI components and utilities for building Salt applications.
 
+pub mod accessibility;
+pub mod capture;
 pub mod color;
 pub mod components;
 pub mod context;
+pub mod cursor;
+pub mod focus;
+pub mod frame;
 pub mod gesture;
+pub mod history;
+pub mod scheduler;
+pub mod svg;
+pub mod task;
+pub mod text_edit;
+pub mod tooltip;
+pub mod update;
 pub mod view;
 
+pub use accessibility::{Accessibility, RangeValue, Role};
+pub use capture::PointerCapture;
 pub use color::Color;
-pub use components::{path, rect, text};
+pub use components::{path, rect, text, text_input};
 pub use context::{AppCtx, GestureState};
-pub use gesture::{DragPhase, Point};
+pub use cursor::Cursor;
+pub use focus::FocusRingStyle;
+pub use frame::FrameRequests;
+pub use gesture::{DragPhase, EventCtx, EventPhase, GestureInfo, Point};
+pub use history::{History, HistoryStatus};
+pub use scheduler::{Scheduler, TimerHandle};
+pub use svg::{ParseSvgError, SvgElement};
+pub use task::{Executor, LocalExecutor, Tasks, WasmExecutor};
+pub use text_edit::{EditResult, TextEditState};
+pub use tooltip::TooltipStyle;
+pub use update::{DiffLine, Dispatched, Store, Update};
 pub use view::{Shape, ShapeType, TextAlign, View};
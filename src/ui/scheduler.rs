@@ -0,0 +1,188 @@
+//! Timers for Salt UI applications
+//!
+//! This module provides a scheduler for running callbacks after a delay or at an interval.
+//! The scheduler has no clock of its own: the host advances it with the current time,
+//! so native code can drive it with a virtual clock.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Handle identifying a scheduled timer, used to cancel it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerHandle(u64);
+
+struct Timer<T: ?Sized> {
+    handle: TimerHandle,
+    due: f64,
+    interval: Option<f64>,
+    callback: Rc<dyn Fn(&mut T)>,
+}
+
+struct SchedulerInner<T: ?Sized> {
+    now: f64,
+    next_id: u64,
+    timers: Vec<Timer<T>>,
+}
+
+/// Shared timer queue for an application
+///
+/// Cloning the scheduler shares the same queue, so a clone can be moved into
+/// shape callbacks to schedule or cancel timers from there. Times are in milliseconds.
+pub struct Scheduler<T: ?Sized> {
+    inner: Rc<RefCell<SchedulerInner<T>>>,
+}
+
+impl<T: ?Sized> Clone for Scheduler<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized> Default for Scheduler<T> {
+    fn default() -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(SchedulerInner {
+                now: 0.0,
+                next_id: 0,
+                timers: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl<T: ?Sized> Scheduler<T> {
+    /// Create an empty scheduler with its clock at zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current time of the scheduler's clock
+    pub fn now(&self) -> f64 {
+        self.inner.borrow().now
+    }
+
+    /// Move the clock forward to `now` without running any timers
+    ///
+    /// The clock never moves backwards.
+    pub fn advance(&self, now: f64) {
+        let mut inner = self.inner.borrow_mut();
+        inner.now = inner.now.max(now);
+    }
+
+    /// Run `callback` once after `delay`
+    pub fn set_timeout(&self, delay: Duration, callback: impl Fn(&mut T) + 'static) -> TimerHandle {
+        self.schedule(delay, None, Rc::new(callback))
+    }
+
+    /// Run `callback` every `period`, starting one period from now
+    pub fn set_interval(
+        &self,
+        period: Duration,
+        callback: impl Fn(&mut T) + 'static,
+    ) -> TimerHandle {
+        // A zero period would fire forever within a single run
+        let period_ms = (period.as_secs_f64() * 1000.0).max(1.0);
+        self.schedule(period, Some(period_ms), Rc::new(callback))
+    }
+
+    /// Cancel a timer, returning whether it was still pending
+    pub fn cancel(&self, handle: TimerHandle) -> bool {
+        let mut inner = self.inner.borrow_mut();
+        let before = inner.timers.len();
+        inner.timers.retain(|timer| timer.handle != handle);
+        inner.timers.len() != before
+    }
+
+    /// Whether a timer is still waiting to fire
+    pub fn is_pending(&self, handle: TimerHandle) -> bool {
+        self.inner
+            .borrow()
+            .timers
+            .iter()
+            .any(|timer| timer.handle == handle)
+    }
+
+    /// The time at which the earliest timer is due
+    pub fn next_deadline(&self) -> Option<f64> {
+        self.inner
+            .borrow()
+            .timers
+            .iter()
+            .map(|timer| timer.due)
+            .reduce(f64::min)
+    }
+
+    /// Advance the clock to `now`, running every timer that comes due in order
+    ///
+    /// Returns true if any timer ran. Timers scheduled by a running callback are
+    /// timed from the moment that callback was due. An interval that missed several
+    /// periods runs once, then resumes on its next period after `now`, like
+    /// `setInterval` in browsers.
+    pub fn run_due(&self, state: &mut T, now: f64) -> bool {
+        let mut ran = false;
+
+        loop {
+            // Take the timer out before running it, so its callback may use the scheduler
+            let callback = {
+                let mut inner = self.inner.borrow_mut();
+                let next = inner
+                    .timers
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, timer)| timer.due <= now)
+                    .min_by(|(_, a), (_, b)| a.due.total_cmp(&b.due))
+                    .map(|(idx, _)| idx);
+                let Some(idx) = next else {
+                    break;
+                };
+
+                let due = inner.timers[idx].due;
+                inner.now = inner.now.max(due);
+                let callback = inner.timers[idx].callback.clone();
+                match inner.timers[idx].interval {
+                    // An interval that fell behind skips the periods it missed
+                    Some(period) => {
+                        let missed = ((now - due) / period).floor().max(0.0);
+                        let mut next = due + (missed + 1.0) * period;
+                        if next <= now {
+                            next += period;
+                        }
+                        inner.timers[idx].due = next;
+                    }
+                    None => {
+                        inner.timers.remove(idx);
+                    }
+                }
+                callback
+            };
+
+            callback(state);
+            ran = true;
+        }
+
+        self.advance(now);
+        ran
+    }
+
+    fn schedule(
+        &self,
+        delay: Duration,
+        interval: Option<f64>,
+        callback: Rc<dyn Fn(&mut T)>,
+    ) -> TimerHandle {
+        let mut inner = self.inner.borrow_mut();
+        let handle = TimerHandle(inner.next_id);
+        inner.next_id += 1;
+        let due = inner.now + delay.as_secs_f64() * 1000.0;
+        inner.timers.push(Timer {
+            handle,
+            due,
+            interval,
+            callback,
+        });
+        handle
+    }
+}
@@ -0,0 +1,384 @@
+//! SVG element tree for Salt UI
+//!
+//! Views render into an [`SvgElement`] tree before being serialized. The compact
+//! form is what the browser receives; the pretty form puts one element per line
+//! and rounds numbers to a fixed precision, so it diffs cleanly in snapshots.
+
+use std::fmt;
+
+/// An SVG element with its attributes and content
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgElement {
+    /// Tag name, e.g. `"rect"`
+    pub name: String,
+    /// Attributes in the order they are written
+    pub attributes: Vec<(String, String)>,
+    /// Text content, for elements such as `text`
+    pub text: Option<String>,
+    /// Child elements
+    pub children: Vec<SvgElement>,
+}
+
+impl SvgElement {
+    /// Create an element with no attributes or content
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            attributes: Vec::new(),
+            text: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Add an attribute
+    pub fn attr(mut self, name: impl Into<String>, value: impl fmt::Display) -> Self {
+        self.attributes.push((name.into(), value.to_string()));
+        self
+    }
+
+    /// Set the text content
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Add a child element
+    pub fn child(mut self, child: SvgElement) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Look up an attribute value by name
+    pub fn get_attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Copy of the tree with every number in attribute values rounded to `precision` decimals
+    pub fn normalized(&self, precision: usize) -> SvgElement {
+        SvgElement {
+            name: self.name.clone(),
+            attributes: self
+                .attributes
+                .iter()
+                .map(|(key, value)| (key.clone(), round_numbers(value, precision)))
+                .collect(),
+            text: self.text.clone(),
+            children: self
+                .children
+                .iter()
+                .map(|child| child.normalized(precision))
+                .collect(),
+        }
+    }
+
+    /// Serialize on a single line, as sent to the browser
+    pub fn to_compact_string(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, None, 0);
+        out
+    }
+
+    /// Serialize with one element per line and numbers rounded to `precision` decimals
+    pub fn to_pretty_string(&self, precision: usize) -> String {
+        let mut out = String::new();
+        self.normalized(precision).write(&mut out, Some("  "), 0);
+        out.push('\n');
+        out
+    }
+
+    /// Parse SVG produced by either serializer
+    pub fn parse(source: &str) -> Result<SvgElement, ParseSvgError> {
+        let mut parser = Parser { source, pos: 0 };
+        parser.skip_whitespace();
+        let element = parser.element()?;
+        parser.skip_whitespace();
+        if parser.pos != source.len() {
+            return Err(parser.error("trailing content after the root element"));
+        }
+        Ok(element)
+    }
+
+    /// Describe how `other` differs from this tree, one line per change
+    ///
+    /// Children are compared by position. An empty result means the trees are equal.
+    pub fn diff(&self, other: &SvgElement) -> Vec<String> {
+        let mut changes = Vec::new();
+        diff_elements(&self.name, self, other, &mut changes);
+        changes
+    }
+
+    fn write(&self, out: &mut String, indent: Option<&str>, depth: usize) {
+        if let Some(indent) = indent {
+            out.push_str(&indent.repeat(depth));
+        }
+        out.push('<');
+        out.push_str(&self.name);
+        for (key, value) in &self.attributes {
+            out.push_str(&format!(r#" {}="{}""#, key, escape(value)));
+        }
+
+        if self.text.is_none() && self.children.is_empty() {
+            out.push_str("/>");
+            return;
+        }
+        out.push('>');
+
+        // Indenting next to text content would change the text, so mixed
+        // content stays on one line
+        let indent = if self.text.is_some() { None } else { indent };
+        if let Some(text) = &self.text {
+            out.push_str(&escape(text));
+        }
+        if !self.children.is_empty() {
+            for child in &self.children {
+                if indent.is_some() {
+                    out.push('\n');
+                }
+                child.write(out, indent, depth + 1);
+            }
+            if let Some(indent) = indent {
+                out.push('\n');
+                out.push_str(&indent.repeat(depth));
+            }
+        }
+
+        out.push_str("</");
+        out.push_str(&self.name);
+        out.push('>');
+    }
+}
+
+impl fmt::Display for SvgElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_compact_string())
+    }
+}
+
+/// Error returned when SVG text cannot be parsed into an element tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSvgError {
+    /// Byte offset where parsing failed
+    pub position: usize,
+    /// What was expected there
+    pub message: String,
+}
+
+impl fmt::Display for ParseSvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid SVG at byte {}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for ParseSvgError {}
+
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.source[self.pos..]
+    }
+
+    fn error(&self, message: &str) -> ParseSvgError {
+        ParseSvgError {
+            position: self.pos,
+            message: message.to_string(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ParseSvgError> {
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", token)))
+        }
+    }
+
+    fn name(&mut self) -> Result<String, ParseSvgError> {
+        let len = self
+            .rest()
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')))
+            .unwrap_or(self.rest().len());
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+        let name = self.rest()[..len].to_string();
+        self.pos += len;
+        Ok(name)
+    }
+
+    fn element(&mut self) -> Result<SvgElement, ParseSvgError> {
+        self.expect("<")?;
+        let mut element = SvgElement::new(self.name()?);
+
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            }
+            if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let key = self.name()?;
+            self.expect("=\"")?;
+            let len = self
+                .rest()
+                .find('"')
+                .ok_or_else(|| self.error("unterminated attribute value"))?;
+            let value = unescape(&self.rest()[..len]);
+            self.pos += len + 1;
+            element.attributes.push((key, value));
+        }
+
+        loop {
+            let len = self
+                .rest()
+                .find('<')
+                .ok_or_else(|| self.error("unterminated element"))?;
+            let text = &self.rest()[..len];
+            if !text.trim().is_empty() {
+                element.text = Some(unescape(text));
+            }
+            self.pos += len;
+
+            if self.rest().starts_with("</") {
+                self.pos += 2;
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(self.error(&format!("expected `</{}>`", element.name)));
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                return Ok(element);
+            }
+            element.children.push(self.element()?);
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Round every number in a space or comma separated value, leaving other tokens alone
+fn round_numbers(value: &str, precision: usize) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut token = String::new();
+    for c in value.chars().chain(std::iter::once(' ')) {
+        if c == ' ' || c == ',' {
+            out.push_str(&round_token(&token, precision));
+            token.clear();
+            out.push(c);
+        } else {
+            token.push(c);
+        }
+    }
+    out.pop();
+    out
+}
+
+fn round_token(token: &str, precision: usize) -> String {
+    let numeric = token
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_digit() || c == '-' || c == '.');
+    match token.parse::<f64>() {
+        Ok(number) if numeric && number.is_finite() => {
+            let rounded = format!("{:.*}", precision, number);
+            let trimmed = if rounded.contains('.') {
+                rounded.trim_end_matches('0').trim_end_matches('.')
+            } else {
+                &rounded
+            };
+            match trimmed {
+                "-0" => "0".to_string(),
+                other => other.to_string(),
+            }
+        }
+        _ => token.to_string(),
+    }
+}
+
+fn diff_elements(path: &str, old: &SvgElement, new: &SvgElement, changes: &mut Vec<String>) {
+    if old.name != new.name {
+        changes.push(format!(
+            "{}: element <{}> replaced by <{}>",
+            path, old.name, new.name
+        ));
+        return;
+    }
+
+    for (key, old_value) in &old.attributes {
+        match new.get_attr(key) {
+            Some(new_value) if new_value != old_value => changes.push(format!(
+                "{}: {}=\"{}\" changed to \"{}\"",
+                path, key, old_value, new_value
+            )),
+            Some(_) => {}
+            None => changes.push(format!("{}: {}=\"{}\" removed", path, key, old_value)),
+        }
+    }
+    for (key, new_value) in &new.attributes {
+        if old.get_attr(key).is_none() {
+            changes.push(format!("{}: {}=\"{}\" added", path, key, new_value));
+        }
+    }
+
+    if old.text != new.text {
+        changes.push(format!(
+            "{}: text {:?} changed to {:?}",
+            path,
+            old.text.as_deref().unwrap_or(""),
+            new.text.as_deref().unwrap_or("")
+        ));
+    }
+
+    let count = old.children.len().max(new.children.len());
+    for idx in 0..count {
+        match (old.children.get(idx), new.children.get(idx)) {
+            (Some(old_child), Some(new_child)) => {
+                let child_path = format!("{} > {}[{}]", path, new_child.name, idx);
+                diff_elements(&child_path, old_child, new_child, changes);
+            }
+            (Some(old_child), None) => changes.push(format!(
+                "{} > {}[{}]: removed {}",
+                path,
+                old_child.name,
+                idx,
+                old_child.to_compact_string()
+            )),
+            (None, Some(new_child)) => changes.push(format!(
+                "{} > {}[{}]: added {}",
+                path,
+                new_child.name,
+                idx,
+                new_child.to_compact_string()
+            )),
+            (None, None) => {}
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! Async tasks for Salt UI applications
+//!
+//! This module lets applications spawn futures whose results are delivered back
+//! into the application state. Futures run on a pluggable executor: in the browser
+//! they run on the JavaScript event loop through wasm-bindgen-futures, while native
+//! code uses a local executor that is driven each time the app is polled.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+/// A boxed future as handed to an executor
+pub type LocalFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Runs futures spawned by an application
+pub trait Executor {
+    /// Start running a future
+    fn spawn(&self, future: LocalFuture);
+
+    /// Make as much progress as possible on spawned futures without waiting
+    ///
+    /// Called whenever the app is polled. Executors driven by the host's own
+    /// event loop can leave this empty.
+    fn run_until_stalled(&self) {}
+}
+
+/// Executor that runs futures on the browser's event loop
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WasmExecutor;
+
+impl Executor for WasmExecutor {
+    fn spawn(&self, future: LocalFuture) {
+        wasm_bindgen_futures::spawn_local(future);
+    }
+}
+
+/// Single-threaded executor that only makes progress when it is run
+///
+/// This is the default outside of WebAssembly, so the same app code can be
+/// exercised in native unit tests by polling the app.
+#[derive(Clone, Default)]
+pub struct LocalExecutor {
+    tasks: Rc<RefCell<Vec<LocalFuture>>>,
+}
+
+impl LocalExecutor {
+    /// Create an executor with no tasks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether any spawned future has not finished yet
+    pub fn has_pending(&self) -> bool {
+        !self.tasks.borrow().is_empty()
+    }
+}
+
+impl Executor for LocalExecutor {
+    fn spawn(&self, future: LocalFuture) {
+        self.tasks.borrow_mut().push(future);
+    }
+
+    fn run_until_stalled(&self) {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            // Take the tasks out while polling, so futures may spawn more
+            let tasks = std::mem::take(&mut *self.tasks.borrow_mut());
+            if tasks.is_empty() {
+                break;
+            }
+
+            let mut progressed = false;
+            let mut pending = Vec::new();
+            for mut task in tasks {
+                match task.as_mut().poll(&mut cx) {
+                    Poll::Ready(()) => progressed = true,
+                    Poll::Pending => pending.push(task),
+                }
+            }
+
+            let mut tasks = self.tasks.borrow_mut();
+            progressed |= !tasks.is_empty();
+            pending.append(&mut tasks);
+            *tasks = pending;
+
+            // A finished or newly spawned task may have unblocked the others
+            if !progressed {
+                break;
+            }
+        }
+    }
+}
+
+/// Waker that does nothing, as the local executor is polled rather than woken
+fn noop_waker() -> Waker {
+    fn raw() -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(|_| raw(), |_| {}, |_| {}, |_| {});
+
+    // SAFETY: none of the vtable functions touch the data pointer
+    unsafe { Waker::from_raw(raw()) }
+}
+
+type Completion<T> = Box<dyn FnOnce(&mut T)>;
+type HostWaker = Rc<dyn Fn()>;
+
+/// Shared handle for spawning tasks against an application's state
+///
+/// Cloning the handle shares the same executor and completion queue, so a clone
+/// can be moved into shape callbacks to spawn tasks from there.
+pub struct Tasks<T: ?Sized> {
+    executor: Rc<RefCell<Rc<dyn Executor>>>,
+    completed: Rc<RefCell<Vec<Completion<T>>>>,
+    waker: Rc<RefCell<Option<HostWaker>>>,
+}
+
+impl<T: ?Sized> Clone for Tasks<T> {
+    fn clone(&self) -> Self {
+        Self {
+            executor: self.executor.clone(),
+            completed: self.completed.clone(),
+            waker: self.waker.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized> Default for Tasks<T> {
+    fn default() -> Self {
+        #[cfg(target_arch = "wasm32")]
+        let executor: Rc<dyn Executor> = Rc::new(WasmExecutor);
+        #[cfg(not(target_arch = "wasm32"))]
+        let executor: Rc<dyn Executor> = Rc::new(LocalExecutor::new());
+
+        Self {
+            executor: Rc::new(RefCell::new(executor)),
+            completed: Rc::new(RefCell::new(Vec::new())),
+            waker: Rc::new(RefCell::new(None)),
+        }
+    }
+}
+
+impl<T: ?Sized> Tasks<T> {
+    /// Create a task handle using the default executor for the target
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the executor that runs newly spawned futures
+    pub fn set_executor(&self, executor: impl Executor + 'static) {
+        *self.executor.borrow_mut() = Rc::new(executor);
+    }
+
+    /// Set the callback used to tell the host a finished task is waiting to be applied
+    pub fn set_waker(&self, waker: Rc<dyn Fn()>) {
+        *self.waker.borrow_mut() = Some(waker);
+    }
+
+    /// Run `future` and hand its output to `on_complete` along with the state
+    ///
+    /// The completion handler runs the next time the app is polled; the host is
+    /// woken to do so as soon as the future finishes.
+    pub fn spawn<F>(&self, future: F, on_complete: impl FnOnce(&mut T, F::Output) + 'static)
+    where
+        F: Future + 'static,
+        T: 'static,
+    {
+        let completed = self.completed.clone();
+        let waker = self.waker.clone();
+        let task = async move {
+            let output = future.await;
+            completed
+                .borrow_mut()
+                .push(Box::new(move |state: &mut T| on_complete(state, output)));
+            let waker = waker.borrow().clone();
+            if let Some(waker) = waker {
+                waker();
+            }
+        };
+
+        let executor = self.executor.borrow().clone();
+        executor.spawn(Box::pin(task));
+    }
+
+    /// Drive the executor and apply the results of finished tasks to the state
+    ///
+    /// Returns true if any completion handler ran.
+    pub fn run_completed(&self, state: &mut T) -> bool {
+        let executor = self.executor.borrow().clone();
+        executor.run_until_stalled();
+
+        let mut ran = false;
+        loop {
+            // Handlers may spawn more tasks, so never hold the queue while running one
+            let next = {
+                let mut completed = self.completed.borrow_mut();
+                (!completed.is_empty()).then(|| completed.remove(0))
+            };
+            let Some(complete) = next else {
+                break;
+            };
+            complete(state);
+            ran = true;
+        }
+        ran
+    }
+}
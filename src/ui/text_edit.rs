@@ -0,0 +1,376 @@
+//! Caret, selection and composition state for text fields
+//!
+//! The value of a text field lives in the application state. The framework
+//! only keeps where the caret and selection are in the focused field, and turns
+//! keys, clipboard and IME events into edited values that are handed back to
+//! the application through the field's `on_input` callback.
+//!
+//! Positions are counted in characters, not bytes.
+
+use crate::{EventType, KeyEvent, TextEvent};
+
+/// Outcome of feeding an event to a text field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditResult {
+    /// The event does not concern the field
+    Ignored,
+    /// The caret, selection or composition changed but the value did not
+    Moved,
+    /// The value changed to the contained string
+    Changed(String),
+}
+
+/// Caret and selection of the focused text field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEditState {
+    /// ID of the text field being edited
+    pub id: u64,
+    /// End of the selection that stays put while it is extended
+    pub anchor: usize,
+    /// Position of the caret, the moving end of the selection
+    pub caret: usize,
+    /// Text being composed by an input method, shown at the caret until committed
+    pub composition: Option<String>,
+}
+
+impl TextEditState {
+    /// Start editing field `id` with the caret at `caret`
+    pub fn new(id: u64, caret: usize) -> Self {
+        Self {
+            id,
+            anchor: caret,
+            caret,
+            composition: None,
+        }
+    }
+
+    /// Keep the caret and selection inside `value`, which the app may have changed
+    pub fn clamp(&mut self, value: &str) {
+        let len = value.chars().count();
+        self.anchor = self.anchor.min(len);
+        self.caret = self.caret.min(len);
+    }
+
+    /// Start and end of the selection, in order
+    pub fn selection(&self) -> (usize, usize) {
+        (self.anchor.min(self.caret), self.anchor.max(self.caret))
+    }
+
+    /// Whether any text is selected
+    pub fn has_selection(&self) -> bool {
+        self.anchor != self.caret
+    }
+
+    /// The selected part of `value`, if anything is selected
+    pub fn selected_text(&self, value: &str) -> Option<String> {
+        let (start, end) = self.selection();
+        (start != end).then(|| value.chars().skip(start).take(end - start).collect())
+    }
+
+    /// Move the caret to `position`, extending the selection when `extend` is true
+    pub fn move_to(&mut self, position: usize, extend: bool) {
+        self.caret = position;
+        if !extend {
+            self.anchor = position;
+        }
+    }
+
+    /// Select the whole of `value`
+    pub fn select_all(&mut self, value: &str) {
+        self.anchor = 0;
+        self.caret = value.chars().count();
+    }
+
+    /// Replace the selection with `text`, leaving the caret after it
+    pub fn replace_selection(&mut self, value: &str, text: &str) -> String {
+        let (start, end) = self.selection();
+        let mut edited: String = value.chars().take(start).collect();
+        edited.push_str(text);
+        edited.extend(value.chars().skip(end));
+        self.move_to(start + text.chars().count(), false);
+        edited
+    }
+
+    /// Apply a key press, returning what changed
+    pub fn handle_key(&mut self, value: &str, multiline: bool, event: &KeyEvent) -> EditResult {
+        if event.event_type != EventType::KeyDown {
+            return EditResult::Ignored;
+        }
+        self.clamp(value);
+        let extend = event.modifiers.shift;
+        let command = event.modifiers.ctrl || event.modifiers.meta;
+        let len = value.chars().count();
+        let (start, end) = self.selection();
+
+        match event.key.as_str() {
+            "a" | "A" if command => {
+                self.select_all(value);
+                EditResult::Moved
+            }
+            // Other shortcuts, including copy, cut and paste, are left to the host
+            _ if command || event.modifiers.alt => EditResult::Ignored,
+            "Backspace" if self.has_selection() => self.replace(value, ""),
+            "Backspace" if self.caret > 0 => {
+                self.anchor = self.caret - 1;
+                self.replace(value, "")
+            }
+            "Delete" if self.has_selection() => self.replace(value, ""),
+            "Delete" if self.caret < len => {
+                self.anchor = self.caret + 1;
+                self.replace(value, "")
+            }
+            "Backspace" | "Delete" => EditResult::Moved,
+            "ArrowLeft" if self.has_selection() && !extend => self.moved(start, false),
+            "ArrowLeft" => self.moved(self.caret.saturating_sub(1), extend),
+            "ArrowRight" if self.has_selection() && !extend => self.moved(end, false),
+            "ArrowRight" => self.moved((self.caret + 1).min(len), extend),
+            "Home" => {
+                let (line, _) = line_col(value, self.caret);
+                self.moved(position_at(value, line, 0), extend)
+            }
+            "End" => {
+                let (line, _) = line_col(value, self.caret);
+                self.moved(position_at(value, line, usize::MAX), extend)
+            }
+            "ArrowUp" | "ArrowDown" if multiline => {
+                let (line, col) = line_col(value, self.caret);
+                let target = match event.key.as_str() {
+                    "ArrowUp" if line == 0 => return self.moved(0, extend),
+                    "ArrowUp" => line - 1,
+                    _ if line + 1 >= value.split('\n').count() => return self.moved(len, extend),
+                    _ => line + 1,
+                };
+                self.moved(position_at(value, target, col), extend)
+            }
+            "Enter" if multiline => self.replace(value, "\n"),
+            key if key.chars().count() == 1 => self.replace(value, key),
+            _ => EditResult::Ignored,
+        }
+    }
+
+    /// Apply a clipboard, input or composition event, returning what changed
+    pub fn handle_text(&mut self, value: &str, multiline: bool, event: &TextEvent) -> EditResult {
+        self.clamp(value);
+        let data = if multiline {
+            event.data.clone()
+        } else {
+            event.data.replace(['\r', '\n'], " ")
+        };
+
+        match event.event_type {
+            EventType::Input | EventType::Paste if !data.is_empty() => self.replace(value, &data),
+            EventType::Cut if self.has_selection() => self.replace(value, ""),
+            EventType::CompositionStart => {
+                self.composition = Some(String::new());
+                if self.has_selection() {
+                    self.replace(value, "")
+                } else {
+                    EditResult::Moved
+                }
+            }
+            EventType::CompositionUpdate => {
+                self.composition = Some(data);
+                EditResult::Moved
+            }
+            EventType::CompositionEnd => {
+                self.composition = None;
+                if data.is_empty() {
+                    EditResult::Moved
+                } else {
+                    self.replace(value, &data)
+                }
+            }
+            _ => EditResult::Ignored,
+        }
+    }
+
+    fn replace(&mut self, value: &str, text: &str) -> EditResult {
+        EditResult::Changed(self.replace_selection(value, text))
+    }
+
+    fn moved(&mut self, position: usize, extend: bool) -> EditResult {
+        self.move_to(position, extend);
+        EditResult::Moved
+    }
+}
+
+/// Line and column of the character position `position` in `value`
+pub fn line_col(value: &str, position: usize) -> (usize, usize) {
+    let mut line = 0;
+    let mut col = 0;
+    for c in value.chars().take(position) {
+        if c == '\n' {
+            line += 1;
+            col = 0;
+        } else {
+            col += 1;
+        }
+    }
+    (line, col)
+}
+
+/// Character position of `col` on `line` of `value`, clamped to the line's end
+pub fn position_at(value: &str, line: usize, col: usize) -> usize {
+    let mut position = 0;
+    for (idx, text) in value.split('\n').enumerate() {
+        let len = text.chars().count();
+        if idx == line {
+            return position + col.min(len);
+        }
+        position += len + 1;
+    }
+    value.chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Modifiers;
+
+    fn key(key: &str, modifiers: Modifiers) -> KeyEvent {
+        KeyEvent {
+            event_type: EventType::KeyDown,
+            key: key.to_string(),
+            code: String::new(),
+            repeat: false,
+            modifiers,
+            timestamp: 0.0,
+        }
+    }
+
+    fn press(edit: &mut TextEditState, value: &str, name: &str) -> EditResult {
+        edit.handle_key(value, false, &key(name, Modifiers::default()))
+    }
+
+    fn shift(edit: &mut TextEditState, value: &str, name: &str) -> EditResult {
+        let modifiers = Modifiers {
+            shift: true,
+            ..Modifiers::default()
+        };
+        edit.handle_key(value, false, &key(name, modifiers))
+    }
+
+    fn changed(value: &str) -> EditResult {
+        EditResult::Changed(value.to_string())
+    }
+
+    #[test]
+    fn arrows_move_the_caret_within_the_value() {
+        let mut edit = TextEditState::new(1, 0);
+        assert_eq!(press(&mut edit, "héllo", "ArrowLeft"), EditResult::Moved);
+        assert_eq!(edit.caret, 0);
+        press(&mut edit, "héllo", "ArrowRight");
+        press(&mut edit, "héllo", "ArrowRight");
+        assert_eq!(edit.caret, 2, "counted in characters");
+        press(&mut edit, "héllo", "End");
+        assert_eq!(edit.caret, 5);
+        press(&mut edit, "héllo", "ArrowRight");
+        assert_eq!(edit.caret, 5);
+        press(&mut edit, "héllo", "Home");
+        assert_eq!((edit.anchor, edit.caret), (0, 0));
+    }
+
+    #[test]
+    fn arrows_collapse_a_selection_to_its_ends() {
+        let mut edit = TextEditState::new(1, 1);
+        shift(&mut edit, "hello", "ArrowRight");
+        shift(&mut edit, "hello", "ArrowRight");
+        assert_eq!(edit.selection(), (1, 3));
+
+        press(&mut edit, "hello", "ArrowLeft");
+        assert_eq!((edit.anchor, edit.caret), (1, 1));
+        shift(&mut edit, "hello", "End");
+        press(&mut edit, "hello", "ArrowRight");
+        assert_eq!((edit.anchor, edit.caret), (5, 5));
+    }
+
+    #[test]
+    fn vertical_arrows_keep_the_column_in_multiline_fields() {
+        let value = "one\nfour\nsix";
+        let mut edit = TextEditState::new(1, 2);
+        let down = key("ArrowDown", Modifiers::default());
+        edit.handle_key(value, true, &down);
+        assert_eq!(line_col(value, edit.caret), (1, 2));
+        edit.handle_key(value, true, &down);
+        assert_eq!(line_col(value, edit.caret), (2, 2));
+        edit.handle_key(value, true, &down);
+        assert_eq!(edit.caret, value.chars().count());
+        assert_eq!(press(&mut edit, value, "ArrowDown"), EditResult::Ignored);
+    }
+
+    #[test]
+    fn typing_replaces_the_selection() {
+        let mut edit = TextEditState::new(1, 0);
+        edit.move_to(6, false);
+        edit.move_to(11, true);
+        assert_eq!(press(&mut edit, "hello world", "W"), changed("hello W"));
+        assert_eq!((edit.anchor, edit.caret), (7, 7));
+
+        edit.select_all("hello W");
+        assert_eq!(edit.replace_selection("hello W", "hi"), "hi");
+        assert_eq!(edit.caret, 2);
+    }
+
+    #[test]
+    fn backspace_and_delete_stop_at_the_edges() {
+        let mut edit = TextEditState::new(1, 0);
+        assert_eq!(press(&mut edit, "abc", "Backspace"), EditResult::Moved);
+        assert_eq!(press(&mut edit, "abc", "Delete"), changed("bc"));
+        assert_eq!(edit.caret, 0);
+
+        edit.move_to(2, false);
+        assert_eq!(press(&mut edit, "bc", "Delete"), EditResult::Moved);
+        assert_eq!(press(&mut edit, "bc", "Backspace"), changed("b"));
+        assert_eq!(edit.caret, 1);
+    }
+
+    #[test]
+    fn backspace_and_delete_remove_the_selection() {
+        let mut edit = TextEditState::new(1, 1);
+        edit.move_to(3, true);
+        assert_eq!(press(&mut edit, "abcd", "Backspace"), changed("ad"));
+        assert_eq!(edit.caret, 1);
+
+        edit.move_to(0, true);
+        assert_eq!(press(&mut edit, "ad", "Delete"), changed("d"));
+        assert_eq!(edit.caret, 0);
+    }
+
+    #[test]
+    fn paste_turns_newlines_into_spaces_on_one_line() {
+        let paste = TextEvent {
+            event_type: EventType::Paste,
+            data: "two\r\nlines".to_string(),
+        };
+        let mut edit = TextEditState::new(1, 1);
+        assert_eq!(
+            edit.handle_text("[]", false, &paste),
+            changed("[two  lines]")
+        );
+        assert_eq!(edit.caret, 11);
+
+        let mut edit = TextEditState::new(1, 1);
+        assert_eq!(
+            edit.handle_text("[]", true, &paste),
+            changed("[two\r\nlines]")
+        );
+    }
+
+    #[test]
+    fn selected_text_follows_the_selection() {
+        let mut edit = TextEditState::new(1, 2);
+        assert_eq!(edit.selected_text("naïve"), None);
+        edit.move_to(5, true);
+        assert_eq!(edit.selected_text("naïve").as_deref(), Some("ïve"));
+        edit.move_to(0, true);
+        assert_eq!(edit.selected_text("naïve").as_deref(), Some("na"));
+    }
+
+    #[test]
+    fn clamp_keeps_the_caret_in_a_shorter_value() {
+        let mut edit = TextEditState::new(1, 4);
+        edit.move_to(8, true);
+        edit.clamp("abc");
+        assert_eq!((edit.anchor, edit.caret), (3, 3));
+    }
+}
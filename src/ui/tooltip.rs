@@ -0,0 +1,88 @@
+//! Tooltips for Salt UI
+//!
+//! Shapes set a tooltip with [`Shape::tooltip`](crate::ui::Shape::tooltip). Once
+//! the pointer has rested on such a shape for the configured delay, the tooltip
+//! is drawn in an overlay layer above every other shape, next to the pointer and
+//! kept inside the view.
+
+use crate::ui::color::Color;
+use crate::ui::gesture::Point;
+use crate::ui::svg::SvgElement;
+use crate::Dimensions;
+
+/// Appearance of tooltips
+#[derive(Debug, Clone)]
+pub struct TooltipStyle {
+    /// Fill of the tooltip box
+    pub background: Color,
+    /// Color of the tooltip text
+    pub text_color: Color,
+    pub font_family: String,
+    pub font_size: f32,
+    /// Space between the text and the edge of the box
+    pub padding: f32,
+    pub corner_radius: f32,
+    /// Distance from the pointer to the box
+    pub offset: f32,
+}
+
+impl Default for TooltipStyle {
+    fn default() -> Self {
+        Self {
+            background: Color::new([0.01, 0.01, 0.01, 0.9]),
+            text_color: Color::WHITE,
+            font_family: "sans-serif".to_string(),
+            font_size: 12.0,
+            padding: 6.0,
+            corner_radius: 4.0,
+            offset: 12.0,
+        }
+    }
+}
+
+impl TooltipStyle {
+    /// Build the overlay showing `text` for a pointer at `anchor`
+    ///
+    /// The box sits below and to the right of the pointer, moves above it when
+    /// there is no room below, and is clamped inside `dimensions`.
+    pub fn overlay(&self, text: &str, anchor: Point, dimensions: Dimensions) -> SvgElement {
+        // Same estimate the text shape uses for its bounds
+        let width = text.chars().count() as f32 * self.font_size * 0.6 + self.padding * 2.0;
+        let height = self.font_size * 1.2 + self.padding * 2.0;
+        let max_x = (dimensions.width as f32 - width).max(0.0);
+        let max_y = (dimensions.height as f32 - height).max(0.0);
+
+        let x = (anchor.x + self.offset).clamp(0.0, max_x);
+        let below = anchor.y + self.offset;
+        let y = if below > max_y {
+            anchor.y - self.offset - height
+        } else {
+            below
+        };
+        let y = y.clamp(0.0, max_y);
+
+        SvgElement::new("g")
+            .attr("class", "salt-tooltip")
+            .attr("pointer-events", "none")
+            .child(
+                SvgElement::new("rect")
+                    .attr("x", x)
+                    .attr("y", y)
+                    .attr("width", width)
+                    .attr("height", height)
+                    .attr("rx", self.corner_radius)
+                    .attr("ry", self.corner_radius)
+                    .attr("fill", format!("{:x}", self.background.to_rgba8())),
+            )
+            .child(
+                SvgElement::new("text")
+                    .attr("x", x + self.padding)
+                    .attr("y", y + self.padding + self.font_size)
+                    .attr("font-family", &self.font_family)
+                    .attr("font-size", self.font_size)
+                    .attr("fill", format!("{:x}", self.text_color.to_rgba8()))
+                    .attr("text-anchor", "start")
+                    .text(text),
+            )
+    }
+}
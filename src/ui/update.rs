@@ -0,0 +1,204 @@
+//! Message based state updates for Salt UI applications
+//!
+//! As an alternative to closure callbacks, shapes can carry plain message values
+//! that are fed to an [`Update`] implementation. Wrapping the state in a [`Store`]
+//! logs every message, so a session can be inspected, replayed and diffed.
+
+use std::fmt;
+use std::ops::Deref;
+
+use crate::ui::view::Shape;
+
+/// State that changes only in response to messages
+pub trait Update {
+    /// The messages this state understands
+    type Msg: Clone + fmt::Debug + 'static;
+
+    /// Apply a message to the state
+    fn update(&mut self, msg: Self::Msg);
+}
+
+/// A single line of a state diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Removed(String),
+    Added(String),
+}
+
+impl fmt::Display for DiffLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiffLine::Removed(line) => write!(f, "- {}", line),
+            DiffLine::Added(line) => write!(f, "+ {}", line),
+        }
+    }
+}
+
+/// A message that was dispatched to a store
+#[derive(Debug, Clone)]
+pub struct Dispatched<Msg> {
+    /// The message itself
+    pub msg: Msg,
+    /// How the state changed, when the store is tracing
+    pub diff: Vec<DiffLine>,
+}
+
+/// State wrapper that routes messages through [`Update`] and records them
+///
+/// Use `Store<S>` as the application state and attach messages to shapes with
+/// [`Shape::on_click_msg`] and friends. The wrapped state is readable through
+/// `Deref`, but only changes through [`Store::dispatch`].
+pub struct Store<S: Update> {
+    state: S,
+    log: Vec<Dispatched<S::Msg>>,
+    describe: Option<fn(&S) -> String>,
+}
+
+impl<S: Update> Store<S> {
+    /// Wrap a state with an empty message log
+    pub fn new(state: S) -> Self {
+        Self {
+            state,
+            log: Vec::new(),
+            describe: None,
+        }
+    }
+
+    /// Record a diff of the state after every message, using `describe` to print it
+    pub fn trace_with(mut self, describe: fn(&S) -> String) -> Self {
+        self.describe = Some(describe);
+        self
+    }
+
+    /// Record a diff of the state's `Debug` output after every message
+    pub fn trace(self) -> Self
+    where
+        S: fmt::Debug,
+    {
+        self.trace_with(|state| format!("{:#?}", state))
+    }
+
+    /// Log a message and apply it to the state
+    pub fn dispatch(&mut self, msg: S::Msg) {
+        let before = self.describe.map(|describe| describe(&self.state));
+        self.state.update(msg.clone());
+        let diff = match (before, self.describe) {
+            (Some(before), Some(describe)) => diff_lines(&before, &describe(&self.state)),
+            _ => Vec::new(),
+        };
+        self.log.push(Dispatched { msg, diff });
+    }
+
+    /// Every message dispatched so far, in order
+    pub fn log(&self) -> &[Dispatched<S::Msg>] {
+        &self.log
+    }
+
+    /// The messages dispatched so far, in order
+    pub fn messages(&self) -> impl Iterator<Item = &S::Msg> {
+        self.log.iter().map(|entry| &entry.msg)
+    }
+
+    /// Forget the logged messages
+    pub fn clear_log(&mut self) {
+        self.log.clear();
+    }
+
+    /// Apply `messages` in order to `initial`, logging them as if they were dispatched live
+    pub fn replay(&mut self, initial: S, messages: impl IntoIterator<Item = S::Msg>) {
+        self.state = initial;
+        self.log.clear();
+        for msg in messages {
+            self.dispatch(msg);
+        }
+    }
+
+    /// The wrapped state
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Unwrap the state, dropping the log
+    pub fn into_inner(self) -> S {
+        self.state
+    }
+}
+
+impl<S: Update + Default> Default for Store<S> {
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S: Update> Deref for Store<S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.state
+    }
+}
+
+impl<S: Update + fmt::Debug> fmt::Debug for Store<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Store")
+            .field("state", &self.state)
+            .field("log", &self.log)
+            .finish()
+    }
+}
+
+impl<S: Update + 'static> Shape<Store<S>> {
+    /// Dispatch `msg` when the shape is clicked
+    pub fn on_click_msg(self, msg: S::Msg) -> Self {
+        self.on_click(move |store| store.dispatch(msg.clone()))
+    }
+
+    /// Dispatch `msg` when the shape is double clicked
+    pub fn on_double_click_msg(self, msg: S::Msg) -> Self {
+        self.on_double_click(move |store| store.dispatch(msg.clone()))
+    }
+
+    /// Dispatch `msg` when the shape is long pressed
+    pub fn on_long_press_msg(self, msg: S::Msg) -> Self {
+        self.on_long_press(move |store, _| store.dispatch(msg.clone()))
+    }
+
+    /// Dispatch the message built by `msg` when the hover state changes
+    pub fn on_hover_msg(self, msg: impl Fn(bool) -> S::Msg + 'static) -> Self {
+        self.on_hover(move |store, hovered, _| store.dispatch(msg(hovered)))
+    }
+}
+
+/// Line based diff of two descriptions, from their longest common subsequence
+fn diff_lines(before: &str, after: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+
+    // lengths[i][j] is the common subsequence length of old[i..] and new[j..]
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            diff.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    diff
+}
@@ -2,11 +2,16 @@
 //!
 //! This module provides the View component for rendering shapes in Salt applications.
 
-use crate::ui::components::{PathBuilder, RectBuilder, TextBuilder};
-use crate::ui::gesture::{DragPhase, Point};
-use crate::Dimensions;
+use crate::ui::accessibility::{Accessibility, RangeValue, Role};
+use crate::ui::components::{PathBuilder, RectBuilder, TextBuilder, TextInputBuilder};
+use crate::ui::cursor::Cursor;
+use crate::ui::gesture::{DragPhase, EventCtx, GestureInfo, PinchGesture, Point, ScrollEvent};
+use crate::ui::svg::SvgElement;
+use crate::{Dimensions, KeyEvent};
 
-use crate::ui::gesture::callbacks::{OnClick, OnDrag, OnHover};
+use crate::ui::gesture::callbacks::{
+    OnClick, OnDoubleClick, OnDrag, OnHover, OnInput, OnKey, OnLongPress, OnPinch, OnScroll,
+};
 
 /// Represents an SVG shape
 #[derive(Clone)]
@@ -15,12 +20,38 @@ pub struct Shape<T: ?Sized> {
     pub id: u64,
     /// The actual shape data
     pub shape_type: ShapeType,
-    /// Click callback
+    /// ID of the parent shape that clicks bubble up to
+    pub parent: Option<u64>,
+    /// Click callback, run in the target and bubble phases
     pub on_click: OnClick<T>,
+    /// Click callback run in the capture phase, before the target sees the click
+    pub on_click_capture: OnClick<T>,
+    /// Double click callback
+    pub on_double_click: OnDoubleClick<T>,
+    /// Long press callback
+    pub on_long_press: OnLongPress<T>,
     /// Hover callback
     pub on_hover: OnHover<T>,
     /// Drag callback
     pub on_drag: OnDrag<T>,
+    /// Pinch callback
+    pub on_pinch: OnPinch<T>,
+    /// Scroll callback
+    pub on_scroll: OnScroll<T>,
+    /// Keyboard callback, invoked while the shape has focus
+    pub on_key: OnKey<T>,
+    /// Callback receiving the edited value of a text field
+    pub on_input: OnInput<T>,
+    /// Whether the shape can receive keyboard focus
+    pub focusable: bool,
+    /// Position in the Tab order, following the HTML `tabindex` rules
+    pub tab_index: Option<i32>,
+    /// Cursor shown while the pointer is over the shape
+    pub cursor: Option<Cursor>,
+    /// Text shown in a tooltip after the pointer rests on the shape
+    pub tooltip: Option<String>,
+    /// Role, label and state announced by screen readers
+    pub accessibility: Accessibility,
 }
 
 /// Shape types that can be rendered
@@ -32,6 +63,8 @@ pub enum ShapeType {
     Text(TextBuilder),
     /// Path shape
     Path(PathBuilder),
+    /// Editable text field
+    TextInput(TextInputBuilder),
 }
 
 impl<T> Shape<T> {
@@ -40,41 +73,245 @@ impl<T> Shape<T> {
         Self {
             id,
             shape_type,
+            parent: None,
             on_click: None,
+            on_click_capture: None,
+            on_double_click: None,
+            on_long_press: None,
             on_hover: None,
             on_drag: None,
+            on_pinch: None,
+            on_scroll: None,
+            on_key: None,
+            on_input: None,
+            focusable: false,
+            tab_index: None,
+            cursor: None,
+            tooltip: None,
+            accessibility: Accessibility::default(),
         }
     }
 
     // Set the click callback
-    pub fn on_click(mut self, callback: impl Fn(&mut T) + 'static) -> Self {
+    pub fn on_click(self, callback: impl Fn(&mut T) + 'static) -> Self {
+        self.on_click_with(move |state, _| callback(state))
+    }
+
+    /// Set the click callback, receiving the event context
+    ///
+    /// The context carries modifier and button details and controls propagation.
+    pub fn on_click_with(mut self, callback: impl Fn(&mut T, &mut EventCtx) + 'static) -> Self {
         self.on_click = Some(std::rc::Rc::new(callback));
         self
     }
 
+    /// Set the capture phase click callback, which runs before descendants see the click
+    pub fn on_click_capture(mut self, callback: impl Fn(&mut T, &mut EventCtx) + 'static) -> Self {
+        self.on_click_capture = Some(std::rc::Rc::new(callback));
+        self
+    }
+
+    /// Set the parent shape that clicks on this shape propagate through
+    pub fn parent(mut self, parent: u64) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Set the double click callback
+    pub fn on_double_click(mut self, callback: impl Fn(&mut T) + 'static) -> Self {
+        self.on_double_click = Some(std::rc::Rc::new(callback));
+        self
+    }
+
+    /// Set the long press callback, receiving the point where the press started
+    pub fn on_long_press(mut self, callback: impl Fn(&mut T, Point) + 'static) -> Self {
+        self.on_long_press = Some(std::rc::Rc::new(callback));
+        self
+    }
+
     /// Set the hover callback
-    pub fn on_hover(mut self, callback: impl Fn(&mut T, bool, Point) + 'static) -> Self {
+    pub fn on_hover(self, callback: impl Fn(&mut T, bool, Point) + 'static) -> Self {
+        self.on_hover_with(move |state, hovered, point, _| callback(state, hovered, point))
+    }
+
+    /// Set the hover callback, receiving modifier and button details
+    pub fn on_hover_with(
+        mut self,
+        callback: impl Fn(&mut T, bool, Point, &GestureInfo) + 'static,
+    ) -> Self {
         self.on_hover = Some(std::rc::Rc::new(callback));
         self
     }
 
     /// Set the drag callback
-    pub fn on_drag(mut self, callback: impl Fn(&mut T, DragPhase, Point, Point) + 'static) -> Self {
+    pub fn on_drag(self, callback: impl Fn(&mut T, DragPhase, Point, Point) + 'static) -> Self {
+        self.on_drag_with(move |state, phase, start, current, _| {
+            callback(state, phase, start, current)
+        })
+    }
+
+    /// Set the drag callback, receiving modifier and button details
+    pub fn on_drag_with(
+        mut self,
+        callback: impl Fn(&mut T, DragPhase, Point, Point, &GestureInfo) + 'static,
+    ) -> Self {
         self.on_drag = Some(std::rc::Rc::new(callback));
         self
     }
 
+    /// Set the pinch callback
+    ///
+    /// Receives two-finger touch pinches and trackpad ctrl+wheel zooming alike.
+    pub fn on_pinch(
+        mut self,
+        callback: impl Fn(&mut T, DragPhase, PinchGesture) + 'static,
+    ) -> Self {
+        self.on_pinch = Some(std::rc::Rc::new(callback));
+        self
+    }
+
+    /// Set the scroll callback
+    ///
+    /// Call `stop_propagation` on the event to keep it from reaching shapes underneath.
+    pub fn on_scroll(mut self, callback: impl Fn(&mut T, &mut ScrollEvent) + 'static) -> Self {
+        self.on_scroll = Some(std::rc::Rc::new(callback));
+        self
+    }
+
+    /// Set the keyboard callback
+    ///
+    /// The callback returns true if it handled the key. Setting it makes the shape focusable.
+    pub fn on_key(mut self, callback: impl Fn(&mut T, &KeyEvent) -> bool + 'static) -> Self {
+        self.on_key = Some(std::rc::Rc::new(callback));
+        self.focusable = true;
+        self
+    }
+
+    /// Set the callback receiving the new value after each edit of a text field
+    ///
+    /// The callback should store the value in the state the field is rendered from.
+    pub fn on_input(mut self, callback: impl Fn(&mut T, String) + 'static) -> Self {
+        self.on_input = Some(std::rc::Rc::new(callback));
+        self
+    }
+
+    /// Set whether the shape can receive keyboard focus
+    pub fn focusable(mut self, focusable: bool) -> Self {
+        self.focusable = focusable;
+        self
+    }
+
+    /// Set the shape's position in the Tab order, making it focusable
+    ///
+    /// As with HTML `tabindex`, shapes with a positive index come first in
+    /// ascending order, followed by those with index 0 or none in document
+    /// order. A negative index keeps the shape focusable by pointer but out of
+    /// the Tab order.
+    pub fn tab_index(mut self, tab_index: i32) -> Self {
+        self.tab_index = Some(tab_index);
+        self.focusable = true;
+        self
+    }
+
+    /// Set the cursor shown while the pointer is over the shape
+    ///
+    /// Shapes without a cursor of their own use their parent's.
+    pub fn cursor(mut self, cursor: Cursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// Show `text` in a tooltip once the pointer has rested on the shape
+    ///
+    /// The delay is `GestureConfig::tooltip_delay`. Shapes without a tooltip of
+    /// their own show their parent's.
+    pub fn tooltip(mut self, text: impl Into<String>) -> Self {
+        self.tooltip = Some(text.into());
+        self
+    }
+
+    /// Set the ARIA role announced for the shape
+    pub fn role(mut self, role: Role) -> Self {
+        self.accessibility.role = Some(role);
+        self
+    }
+
+    /// Set the accessible name, rendered as the shape's `<title>`
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.accessibility.label = Some(label.into());
+        self
+    }
+
+    /// Set a longer accessible description, rendered as the shape's `<desc>`
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.accessibility.description = Some(description.into());
+        self
+    }
+
+    /// Mark a toggle button as pressed or not
+    pub fn pressed(mut self, pressed: bool) -> Self {
+        self.accessibility.pressed = Some(pressed);
+        self
+    }
+
+    /// Mark a checkbox, switch or radio button as checked or not
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.accessibility.checked = Some(checked);
+        self
+    }
+
+    /// Mark the shape as disabled for assistive technology
+    ///
+    /// This only changes what is announced; callbacks still run.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.accessibility.disabled = disabled;
+        self
+    }
+
+    /// Set the current value and range of a slider or similar widget
+    pub fn value(mut self, now: f32, min: f32, max: f32) -> Self {
+        self.accessibility.value = Some(RangeValue { now, min, max });
+        self
+    }
+
     /// Execute the on_click callback if present
-    pub(crate) fn run_on_click(&self, state: &mut T) {
+    pub(crate) fn run_on_click(&self, state: &mut T, event: &mut EventCtx) {
         if let Some(func) = &self.on_click {
+            func(state, event);
+        }
+    }
+
+    /// Execute the on_click_capture callback if present
+    pub(crate) fn run_on_click_capture(&self, state: &mut T, event: &mut EventCtx) {
+        if let Some(func) = &self.on_click_capture {
+            func(state, event);
+        }
+    }
+
+    /// Execute the on_double_click callback if present
+    pub(crate) fn run_on_double_click(&self, state: &mut T) {
+        if let Some(func) = &self.on_double_click {
             func(state);
         }
     }
 
+    /// Execute the on_long_press callback if present
+    pub(crate) fn run_on_long_press(&self, state: &mut T, point: Point) {
+        if let Some(func) = &self.on_long_press {
+            func(state, point);
+        }
+    }
+
     /// Execute the on_hover callback if present
-    pub(crate) fn run_on_hover(&self, state: &mut T, hovered: bool, point: Point) {
+    pub(crate) fn run_on_hover(
+        &self,
+        state: &mut T,
+        hovered: bool,
+        point: Point,
+        info: &GestureInfo,
+    ) {
         if let Some(func) = &self.on_hover {
-            func(state, hovered, point);
+            func(state, hovered, point, info);
         }
     }
 
@@ -85,16 +322,88 @@ impl<T> Shape<T> {
         phase: DragPhase,
         start: Point,
         current: Point,
+        info: &GestureInfo,
     ) {
         if let Some(func) = &self.on_drag {
-            func(state, phase, start, current);
+            func(state, phase, start, current, info);
+        }
+    }
+
+    /// Execute the on_pinch callback if present
+    pub(crate) fn run_on_pinch(&self, state: &mut T, phase: DragPhase, gesture: PinchGesture) {
+        if let Some(func) = &self.on_pinch {
+            func(state, phase, gesture);
+        }
+    }
+
+    /// Execute the on_scroll callback if present, returning whether it ran
+    pub(crate) fn run_on_scroll(&self, state: &mut T, event: &mut ScrollEvent) -> bool {
+        if let Some(func) = &self.on_scroll {
+            func(state, event);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Execute the on_key callback if present
+    pub(crate) fn run_on_key(&self, state: &mut T, event: &KeyEvent) -> bool {
+        if let Some(func) = &self.on_key {
+            func(state, event)
+        } else {
+            false
+        }
+    }
+
+    /// Execute the on_input callback if present
+    pub(crate) fn run_on_input(&self, state: &mut T, value: String) {
+        if let Some(func) = &self.on_input {
+            func(state, value);
+        }
+    }
+
+    /// Bounding box of the shape as `(min_x, min_y, max_x, max_y)`, if it has any extent
+    pub fn bounds(&self) -> Option<(f32, f32, f32, f32)> {
+        match &self.shape_type {
+            ShapeType::Rect(rect) => Some(rect.bounds()),
+            ShapeType::Text(text) => Some(text.bounds()),
+            ShapeType::Path(path) => path.bounds,
+            ShapeType::TextInput(input) => Some(input.bounds()),
+        }
+    }
+
+    /// Center of the shape's bounding box
+    pub fn center(&self) -> Option<Point> {
+        self.bounds().map(|(min_x, min_y, max_x, max_y)| Point {
+            x: (min_x + max_x) / 2.0,
+            y: (min_y + max_y) / 2.0,
+        })
+    }
+
+    /// The text content of the shape, if it is a text shape
+    pub fn text(&self) -> Option<&str> {
+        match &self.shape_type {
+            ShapeType::Text(text) => Some(&text.text),
+            _ => None,
         }
     }
 
     /// Test if a point hits this shape
     pub fn hit_test(&self, x: f32, y: f32) -> bool {
         // Only hit test if we have at least one interactive handler
-        if self.on_click.is_none() && self.on_hover.is_none() && self.on_drag.is_none() {
+        if self.on_click.is_none()
+            && self.on_click_capture.is_none()
+            && self.on_double_click.is_none()
+            && self.on_long_press.is_none()
+            && self.on_hover.is_none()
+            && self.on_drag.is_none()
+            && self.on_pinch.is_none()
+            && self.on_scroll.is_none()
+            && self.on_key.is_none()
+            && !self.focusable
+            && self.cursor.is_none()
+            && self.tooltip.is_none()
+        {
             return false;
         }
 
@@ -102,6 +411,7 @@ impl<T> Shape<T> {
             ShapeType::Rect(rect) => rect.hit_test_shape(x, y),
             ShapeType::Text(text) => text.hit_test_shape(x, y),
             ShapeType::Path(path) => path.hit_test_shape(x, y),
+            ShapeType::TextInput(input) => input.hit_test_shape(x, y),
         }
     }
 }
@@ -157,46 +467,137 @@ impl<T> View<T> {
         None
     }
 
+    /// Test if a point hits any shape in the view, honoring pointer capture
+    /// While a shape holds the capture it is hit wherever the point is, as long as it is in the view
+    pub fn hit_test_captured(&self, capture: Option<u64>, x: f32, y: f32) -> Option<(usize, u64)> {
+        let captured = capture.and_then(|id| self.find_shape_by_id(id).map(|idx| (idx, id)));
+        captured.or_else(|| self.hit_test_with_id(x, y))
+    }
+
+    /// Test a point against every shape in the view
+    /// Returns the indices and IDs of all hit shapes, ordered top to bottom
+    pub fn hit_test_all(&self, x: f32, y: f32) -> Vec<(usize, u64)> {
+        self.shapes
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, shape)| shape.hit_test(x, y))
+            .map(|(idx, shape)| (idx, shape.id))
+            .collect()
+    }
+
+    /// Collect the propagation path of the shape at `idx`
+    /// Returns the indices of the shape and its ancestors, ordered from the shape up to the root
+    pub fn propagation_path(&self, idx: usize) -> Vec<usize> {
+        let mut path = vec![idx];
+        let mut current = self.shapes[idx].parent;
+
+        // Bound the walk by the shape count so a parent cycle cannot loop forever
+        while let Some(parent_id) = current {
+            if path.len() > self.shapes.len() {
+                break;
+            }
+            let Some(parent_idx) = self.find_shape_by_id(parent_id) else {
+                break;
+            };
+            path.push(parent_idx);
+            current = self.shapes[parent_idx].parent;
+        }
+
+        path
+    }
+
+    /// The cursor for the shape at `idx`, inherited from its nearest ancestor that sets one
+    pub fn cursor_for(&self, idx: usize) -> Option<Cursor> {
+        self.propagation_path(idx)
+            .into_iter()
+            .find_map(|idx| self.shapes[idx].cursor)
+    }
+
+    /// The tooltip for the shape at `idx`, inherited from its nearest ancestor that sets one
+    pub fn tooltip_for(&self, idx: usize) -> Option<&str> {
+        self.propagation_path(idx)
+            .into_iter()
+            .find_map(|idx| self.shapes[idx].tooltip.as_deref())
+    }
+
     /// Find the index of a shape by its ID
     pub fn find_shape_by_id(&self, id: u64) -> Option<usize> {
         self.shapes.iter().position(|shape| shape.id == id)
     }
 
-    /// Render the view to SVG
-    pub fn render(&self, dimensions: Dimensions) -> String {
-        // Initialize SVG with header and viewport
-        let mut svg = format!(
-            r#"<svg xmlns="http://www.w3.org/2000/svg" width="100%" height="100%" viewBox="0 0 {} {}">"#,
-            dimensions.width, dimensions.height,
-        );
+    /// Find the index of the topmost text shape showing exactly `text`
+    pub fn find_shape_by_text(&self, text: &str) -> Option<usize> {
+        self.shapes
+            .iter()
+            .rposition(|shape| shape.text() == Some(text))
+    }
+
+    /// IDs of the shapes reachable with Tab, in Tab order
+    pub fn tab_order(&self) -> Vec<u64> {
+        let mut shapes: Vec<(i32, u64)> = self
+            .shapes
+            .iter()
+            .filter(|shape| shape.focusable)
+            .map(|shape| (shape.tab_index.unwrap_or(0), shape.id))
+            .filter(|&(tab_index, _)| tab_index >= 0)
+            .collect();
+        // The sort is stable, so equal indices keep their document order
+        shapes.sort_by_key(|&(tab_index, _)| (tab_index == 0, tab_index));
+        shapes.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Find the ID of the focusable shape after `current` in Tab order
+    ///
+    /// Walks backwards when `reverse` is true. Returns `None` past the last shape,
+    /// or the first when walking backwards, so focus can move on out of the app.
+    pub fn next_focusable(&self, current: Option<u64>, reverse: bool) -> Option<u64> {
+        let ids = self.tab_order();
+        if ids.is_empty() {
+            return None;
+        }
+
+        let position = current.and_then(|id| ids.iter().position(|&other| other == id));
+        let next = match (position, reverse) {
+            (None, false) => Some(0),
+            (None, true) => Some(ids.len() - 1),
+            (Some(pos), false) => Some(pos + 1).filter(|&next| next < ids.len()),
+            (Some(pos), true) => pos.checked_sub(1),
+        };
+        next.map(|idx| ids[idx])
+    }
+
+    /// Build the SVG element tree for the view
+    pub fn to_svg(&self, dimensions: Dimensions) -> SvgElement {
+        let mut svg = SvgElement::new("svg")
+            .attr("xmlns", "http://www.w3.org/2000/svg")
+            .attr("width", "100%")
+            .attr("height", "100%")
+            .attr(
+                "viewBox",
+                format!("0 0 {} {}", dimensions.width, dimensions.height),
+            );
 
-        // Add shapes to the SVG
         for shape in &self.shapes {
-            match &shape.shape_type {
-                ShapeType::Rect(rect) => {
-                    let mut rect_str = format!(
-                        r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}" ry="{}" fill="{:x}" stroke="{:x}" stroke-width="{}" "#,
-                        rect.x,
-                        rect.y,
-                        rect.width,
-                        rect.height,
-                        rect.corner_radius,
-                        rect.corner_radius,
-                        rect.fill.to_rgba8(),
-                        rect.stroke.to_rgba8(),
-                        rect.stroke_width
-                    );
-
-                    rect_str.push_str("/>");
-                    svg.push_str(&rect_str);
-                }
-                ShapeType::Text(text) => {
-                    svg.push_str(&format!(
-                        r#"<text x="{}" y="{}" font-family="{}" font-size="{}" fill="{:x}" text-anchor="{}">{}</text>"#,
-                        text.x, text.y, text.font_family, text.font_size,
-                        text.fill.to_rgba8(), text.text_anchor, text.text
-                    ));
-                }
+            let element = match &shape.shape_type {
+                ShapeType::Rect(rect) => SvgElement::new("rect")
+                    .attr("x", rect.x)
+                    .attr("y", rect.y)
+                    .attr("width", rect.width)
+                    .attr("height", rect.height)
+                    .attr("rx", rect.corner_radius)
+                    .attr("ry", rect.corner_radius)
+                    .attr("fill", format!("{:x}", rect.fill.to_rgba8()))
+                    .attr("stroke", format!("{:x}", rect.stroke.to_rgba8()))
+                    .attr("stroke-width", rect.stroke_width),
+                ShapeType::Text(text) => SvgElement::new("text")
+                    .attr("x", text.x)
+                    .attr("y", text.y)
+                    .attr("font-family", &text.font_family)
+                    .attr("font-size", text.font_size)
+                    .attr("fill", format!("{:x}", text.fill.to_rgba8()))
+                    .attr("text-anchor", &text.text_anchor)
+                    .text(text.text.clone()),
                 ShapeType::Path(path) => {
                     let path_data = path.commands.iter().fold(String::new(), |mut acc, cmd| {
                         match cmd {
@@ -214,23 +615,32 @@ impl<T> View<T> {
                         acc
                     });
 
-                    svg.push_str(&format!(
-                        r#"<path d="{}" fill="{:x}" stroke="{:x}" stroke-width="{}" />"#,
-                        path_data.trim(),
-                        path.fill.to_rgba8(),
-                        path.stroke.to_rgba8(),
-                        path.stroke_width
-                    ));
+                    SvgElement::new("path")
+                        .attr("d", path_data.trim())
+                        .attr("fill", format!("{:x}", path.fill.to_rgba8()))
+                        .attr("stroke", format!("{:x}", path.stroke.to_rgba8()))
+                        .attr("stroke-width", path.stroke_width)
                 }
-            }
+                ShapeType::TextInput(input) => input.to_svg(None),
+            };
+            svg = svg.child(shape.accessibility.apply(element));
         }
 
-        // Close the SVG tag
-        svg.push_str("</svg>");
-
         svg
     }
 
+    /// Render the view to SVG
+    pub fn render(&self, dimensions: Dimensions) -> String {
+        self.to_svg(dimensions).to_compact_string()
+    }
+
+    /// Render the view to pretty-printed SVG with numbers rounded to `precision` decimals
+    ///
+    /// The output is stable across insignificant float differences, for snapshots and diffs.
+    pub fn render_pretty(&self, dimensions: Dimensions, precision: usize) -> String {
+        self.to_svg(dimensions).to_pretty_string(precision)
+    }
+
     /// Clear all shapes from the view
     pub fn clear(&mut self) {
         self.shapes.clear();
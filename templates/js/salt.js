@@ -4,6 +4,11 @@
 let saltApp = null;
 let container = null;
 let resizeObserver = null;
+let pollTimer = null;
+let frameHandle = null;
+let lastFrameTime = null;
+let textField = null;
+let blurringTextField = false;
 
 // Initialize the application when WASM is loaded
 async function initApp() {
@@ -18,15 +23,22 @@ async function initApp() {
     // Initialize the Rust app
     saltApp = new wasm.SaltApp();
 
+    // Let finished async tasks wake the app outside of event handling
+    saltApp.set_waker(() => queueMicrotask(pollApp));
+
     // Render initial SVG
     renderSvg();
 
     // Set up event handlers
     setupEventListeners();
+    setupTextField();
 
     // Set up resize observer
     setupResizeObserver();
 
+    // Timers, saves and tasks started while the app was created wait on a poll
+    schedulePoll();
+
     console.log("Salt app initialized successfully");
   } catch (err) {
     console.error("Failed to initialize Salt app:", err);
@@ -42,10 +54,166 @@ function renderSvg() {
     Math.floor(width),
     Math.floor(height),
   );
+  container.style.cursor = saltApp.cursor();
+  syncTextField();
+
+  scheduleFrame();
+}
+
+// Keep the hidden text field focused and under the caret while the app edits text,
+// so input methods and virtual keyboards open in the right place
+function syncTextField() {
+  if (!textField) return;
+
+  const caret = saltApp.caret_bounds();
+  if (!caret) {
+    if (document.activeElement === textField) {
+      // The app already let go of focus, so this blur is not reported back
+      blurringTextField = true;
+      textField.blur();
+      blurringTextField = false;
+    }
+    return;
+  }
+
+  const rect = container.getBoundingClientRect();
+  textField.style.left = `${rect.left + caret[0]}px`;
+  textField.style.top = `${rect.top + caret[1]}px`;
+  textField.style.height = `${caret[3] - caret[1]}px`;
+  if (document.activeElement !== textField) {
+    textField.focus({ preventScroll: true });
+  }
+}
+
+// Request an animation frame if the app asked for one
+function scheduleFrame() {
+  if (!saltApp || frameHandle !== null) return;
+
+  // Stop ticking while nothing needs a frame, to save battery
+  if (!saltApp.wants_frame()) {
+    lastFrameTime = null;
+    return;
+  }
+
+  frameHandle = requestAnimationFrame(onFrame);
+}
+
+// Advance the app by the time since the previous frame
+function onFrame(time) {
+  frameHandle = null;
+  const dt = lastFrameTime === null ? 0 : time - lastFrameTime;
+  lastFrameTime = time;
+
+  if (saltApp.tick(dt)) {
+    renderSvg();
+    // The frame may have set a timer or changed state that is saved later
+    schedulePoll();
+  } else {
+    scheduleFrame();
+  }
+}
+
+// Schedule a poll for the next time-based update the app is waiting on
+function schedulePoll() {
+  if (!saltApp) return;
+
+  if (pollTimer !== null) {
+    clearTimeout(pollTimer);
+    pollTimer = null;
+  }
+
+  const deadline = saltApp.next_deadline();
+  if (deadline === undefined) return;
+
+  pollTimer = setTimeout(
+    () => {
+      pollTimer = null;
+      pollApp();
+    },
+    Math.max(0, deadline - performance.now()),
+  );
+}
+
+// Let the app run due timers and apply finished tasks
+function pollApp() {
+  if (!saltApp) return;
+
+  if (saltApp.poll(performance.now())) {
+    renderSvg();
+  }
+  schedulePoll();
 }
 
 // Handle user input events
-function handleEvent(event) {
+function handleEvent(event, eventType = event.type) {
+  if (!saltApp) return;
+
+  const rect = container.getBoundingClientRect();
+  const x = event.clientX - rect.left;
+  const y = event.clientY - rect.top;
+
+  // Pass event to Rust, which rejects event types it does not know
+  let stateChanged;
+  try {
+    stateChanged = saltApp.handle_mouse_event(
+      eventType,
+      x,
+      y,
+      event.pointerId,
+      event.pointerType,
+      event.pressure,
+      event.button,
+      event.buttons,
+      event.shiftKey,
+      event.ctrlKey,
+      event.altKey,
+      event.metaKey,
+      event.timeStamp,
+    );
+  } catch (err) {
+    console.error("Salt rejected event:", err);
+    return;
+  }
+
+  // A callback may ask to suppress the browser's default action
+  if (saltApp.default_prevented()) {
+    event.preventDefault();
+  }
+
+  // Pressing into a text field must not move browser focus away from the hidden field
+  if (eventType === "pointerdown" && saltApp.caret_bounds()) {
+    event.preventDefault();
+  }
+
+  // Re-render if needed
+  if (stateChanged) {
+    renderSvg();
+  }
+
+  if (eventType === "pointerdown" || eventType === "pointermove") {
+    syncPointerCapture(event.pointerId);
+  }
+
+  schedulePoll();
+}
+
+// Mirror the app's pointer capture on the container, so moves and releases
+// outside the container or the browser window are still delivered
+function syncPointerCapture(pointerId) {
+  const captured = saltApp.pointer_captured();
+  try {
+    if (captured && !container.hasPointerCapture(pointerId)) {
+      container.setPointerCapture(pointerId);
+    } else if (!captured && container.hasPointerCapture(pointerId)) {
+      container.releasePointerCapture(pointerId);
+    }
+  } catch (err) {
+    // The pointer is no longer active, so there is nothing to capture
+  }
+}
+
+// Handle mouse wheel and trackpad scroll events
+function handleWheelEvent(event) {
   if (!saltApp) return;
 
   const rect = container.getBoundingClientRect();
@@ -53,24 +221,257 @@ function handleEvent(event) {
   const y = event.clientY - rect.top;
 
   // Pass event to Rust
-  const stateChanged = saltApp.handle_mouse_event(event.type, x, y);
+  const stateChanged = saltApp.handle_wheel_event(
+    x,
+    y,
+    event.deltaX,
+    event.deltaY,
+    event.deltaZ,
+    event.deltaMode,
+    event.shiftKey,
+    event.ctrlKey,
+    event.altKey,
+    event.metaKey,
+    event.timeStamp,
+  );
+
+  // Keep the page from scrolling (or zooming) when a shape consumed the wheel
+  if (stateChanged) {
+    event.preventDefault();
+    renderSvg();
+  }
+
+  schedulePoll();
+}
+
+// Handle keyboard input events
+function handleKeyEvent(event) {
+  if (!saltApp) return;
+
+  // Keys typed into the host page's own fields are not meant for the app
+  const target = event.target;
+  if (
+    target !== textField &&
+    target !== container &&
+    target !== document.body
+  ) {
+    return;
+  }
+
+  // Keys pressed while an input method composes belong to the composition
+  if (event.isComposing || event.keyCode === 229) return;
+
+  // Pass event to Rust, which rejects event types it does not know
+  let stateChanged;
+  try {
+    stateChanged = saltApp.handle_key_event(
+      event.type,
+      event.key,
+      event.code,
+      event.repeat,
+      event.shiftKey,
+      event.ctrlKey,
+      event.altKey,
+      event.metaKey,
+      event.timeStamp,
+    );
+  } catch (err) {
+    console.error("Salt rejected event:", err);
+    return;
+  }
+
+  // Keys the app used, such as Space activating a shape, must not scroll the page
+  if (saltApp.default_prevented()) {
+    event.preventDefault();
+  }
 
   // Re-render if needed
   if (stateChanged) {
     renderSvg();
   }
+
+  schedulePoll();
+}
+
+// Create the hidden field that receives composed, pasted and virtual keyboard text
+function setupTextField() {
+  textField = document.createElement("textarea");
+  textField.setAttribute("autocomplete", "off");
+  textField.setAttribute("autocapitalize", "off");
+  textField.setAttribute("spellcheck", "false");
+  Object.assign(textField.style, {
+    position: "fixed",
+    width: "1px",
+    padding: "0",
+    border: "0",
+    opacity: "0",
+    resize: "none",
+    overflow: "hidden",
+    pointerEvents: "none",
+  });
+  document.body.appendChild(textField);
+
+  ["compositionstart", "compositionupdate", "compositionend"].forEach(
+    (eventType) => {
+      textField.addEventListener(eventType, (event) => {
+        handleTextEvent(eventType, event.data ?? "");
+        if (eventType === "compositionend") {
+          textField.value = "";
+        }
+      });
+    },
+  );
+
+  // Typed keys reach the app as key events, so this only sees text that did not,
+  // such as input from virtual keyboards. Composed text arrives through the
+  // composition events above.
+  textField.addEventListener("input", (event) => {
+    if (event.isComposing) return;
+    if (event.inputType === "insertText" && event.data) {
+      handleTextEvent("input", event.data);
+    }
+    textField.value = "";
+  });
+
+  textField.addEventListener("copy", (event) => {
+    const text = saltApp?.selected_text();
+    if (text == null) return;
+    event.clipboardData.setData("text/plain", text);
+    event.preventDefault();
+  });
+
+  textField.addEventListener("cut", (event) => {
+    const text = saltApp?.selected_text();
+    if (text == null) return;
+    event.clipboardData.setData("text/plain", text);
+    event.preventDefault();
+    handleTextEvent("cut", "");
+  });
+
+  textField.addEventListener("paste", (event) => {
+    event.preventDefault();
+    handleTextEvent("paste", event.clipboardData.getData("text/plain"));
+  });
+
+  // Focus moving to another element takes it from the app's text field too,
+  // while switching windows leaves it in place for when the user comes back
+  textField.addEventListener("blur", () => {
+    if (blurringTextField || !document.hasFocus()) return;
+    handleFocusOut();
+  });
+}
+
+// Forward text input, clipboard and composition events to the focused text field
+function handleTextEvent(eventType, data) {
+  if (!saltApp) return;
+
+  let stateChanged;
+  try {
+    stateChanged = saltApp.handle_text_event(eventType, data);
+  } catch (err) {
+    console.error("Salt rejected event:", err);
+    return;
+  }
+
+  if (stateChanged) {
+    renderSvg();
+  }
+
+  schedulePoll();
+}
+
+// Handle the window losing focus
+function handleBlur() {
+  if (!saltApp) return;
+
+  if (saltApp.handle_blur()) {
+    renderSvg();
+  }
+
+  schedulePoll();
+}
+
+// Clear the app's keyboard focus once focus moves elsewhere on the page
+function handleFocusOut() {
+  if (!saltApp) return;
+
+  if (saltApp.handle_focus_out()) {
+    renderSvg();
+  }
+
+  schedulePoll();
+}
+
+// Forward an application-specific event from the host page
+//
+// Pages send these with
+// `window.dispatchEvent(new CustomEvent("salt-event", { detail: { name, detail } }))`.
+function handleCustomEvent(event) {
+  if (!saltApp) return;
+
+  const { name, detail } = event.detail ?? {};
+  if (typeof name !== "string") {
+    console.error("Salt custom events need a string name:", event.detail);
+    return;
+  }
+
+  const payload =
+    typeof detail === "string" ? detail : JSON.stringify(detail ?? null);
+  if (saltApp.handle_custom_event(name, payload)) {
+    renderSvg();
+  }
+
+  schedulePoll();
 }
 
 // Set up event listeners for user input
 function setupEventListeners() {
   if (!container) return;
 
-  // Mouse events
-  const events = ["click", "mousedown", "mouseup", "mousemove"];
+  // Pointer events cover mouse, pen and touch input
+  const events = ["pointerdown", "pointermove"];
 
   events.forEach((eventType) => {
     container.addEventListener(eventType, handleEvent);
   });
+
+  // Releases are watched on the window so drags ending outside the container still finish
+  const releaseEvents = ["pointerup", "pointercancel"];
+
+  releaseEvents.forEach((eventType) => {
+    window.addEventListener(eventType, handleEvent);
+  });
+
+  // Leaving the window or losing focus cancels drags in progress
+  window.addEventListener("pointerout", (event) => {
+    if (event.relatedTarget === null) {
+      handleEvent(event, "pointerleave");
+    }
+  });
+  window.addEventListener("blur", handleBlur);
+
+  // Pressing elsewhere on the page takes keyboard focus away from the app
+  document.addEventListener("pointerdown", (event) => {
+    if (!container.contains(event.target)) {
+      handleFocusOut();
+    }
+  });
+
+  // Save persisted state before the page goes away
+  window.addEventListener("pagehide", () => saltApp?.flush());
+
+  // Wheel events (non-passive so scrolling shapes can prevent page scroll)
+  container.addEventListener("wheel", handleWheelEvent, { passive: false });
+
+  // Keyboard events
+  const keyEvents = ["keydown", "keyup"];
+
+  keyEvents.forEach((eventType) => {
+    window.addEventListener(eventType, handleKeyEvent);
+  });
+
+  // Events sent on purpose by the host page
+  window.addEventListener("salt-event", handleCustomEvent);
 }
 
 // Update the app when the window is resized
@@ -83,6 +484,7 @@ function setupResizeObserver() {
     for (const entry of entries) {
       if (entry.target === container && saltApp) {
         renderSvg();
+        schedulePoll();
       }
     }
   });
@@ -92,5 +494,29 @@ function setupResizeObserver() {
   }
 }
 
+// Record and replay input sessions from the browser console
+//
+// `salt.startRecording()` begins a session, `salt.stopRecording()` returns it as
+// JSON, and `salt.replay(json, until)` replays it into a fresh app, stopping
+// before entry `until` when given.
+window.salt = {
+  startRecording() {
+    saltApp?.start_recording();
+  },
+
+  stopRecording() {
+    return saltApp?.stop_recording();
+  },
+
+  replay(json, until) {
+    if (!saltApp) return;
+
+    saltApp.replay(json, until);
+    saltApp.set_waker(() => queueMicrotask(pollApp));
+    renderSvg();
+    schedulePoll();
+  },
+};
+
 // Start the application when the document is loaded
 document.addEventListener("DOMContentLoaded", initApp);
@@ -0,0 +1,111 @@
+use salt::testing::TestHarness;
+use salt::ui::{rect, text, AppCtx, Role, SvgElement};
+use salt::{App, Dimensions};
+
+const SAVE: u64 = 1;
+const HEADING: u64 = 2;
+const CAPTION: u64 = 3;
+
+struct LabelledApp {
+    ctx: AppCtx<()>,
+    state: (),
+}
+
+impl App for LabelledApp {
+    type State = ();
+
+    fn new() -> Self {
+        Self {
+            ctx: AppCtx::new(),
+            state: (),
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        self.ctx.view.push(
+            rect()
+                .width(40.0)
+                .height(40.0)
+                .finish(SAVE)
+                .role(Role::Button)
+                .label("Save")
+                .description("Saves the drawing")
+                .pressed(false),
+        );
+        self.ctx.view.push(
+            text()
+                .y(80.0)
+                .text("Drawings")
+                .finish(HEADING)
+                .role(Role::Heading)
+                .label("All drawings"),
+        );
+        self.ctx
+            .view
+            .push(text().y(120.0).text("3 drawings").finish(CAPTION));
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<()>, &mut ()) {
+        (&mut self.ctx, &mut self.state)
+    }
+}
+
+fn rendered() -> SvgElement {
+    TestHarness::<LabelledApp>::new(Dimensions {
+        width: 200,
+        height: 200,
+    })
+    .svg_tree()
+}
+
+fn names(element: &SvgElement) -> Vec<&str> {
+    element
+        .children
+        .iter()
+        .map(|child| child.name.as_str())
+        .collect()
+}
+
+#[test]
+fn role_and_state_become_attributes() {
+    let svg = rendered();
+    let button = &svg.children[0];
+    assert_eq!(button.get_attr("role"), Some("button"));
+    assert_eq!(button.get_attr("aria-pressed"), Some("false"));
+    assert_eq!(button.get_attr("aria-checked"), None);
+}
+
+#[test]
+fn label_and_description_become_title_and_desc() {
+    let svg = rendered();
+    let button = &svg.children[0];
+    assert_eq!(names(button), ["title", "desc"]);
+    assert_eq!(button.children[0].text.as_deref(), Some("Save"));
+    assert_eq!(
+        button.children[1].text.as_deref(),
+        Some("Saves the drawing")
+    );
+}
+
+#[test]
+fn labelled_text_keeps_its_title_first() {
+    let svg = rendered();
+    let heading = &svg.children[1];
+    assert_eq!(heading.get_attr("role"), Some("heading"));
+    assert_eq!(heading.text, None);
+    assert_eq!(names(heading), ["title", "tspan"]);
+    assert_eq!(heading.children[0].text.as_deref(), Some("All drawings"));
+    assert_eq!(heading.children[1].text.as_deref(), Some("Drawings"));
+    assert!(svg
+        .to_compact_string()
+        .contains("<title>All drawings</title><tspan>Drawings</tspan></text>"));
+}
+
+#[test]
+fn unlabelled_text_is_unchanged() {
+    let svg = rendered();
+    let caption = &svg.children[2];
+    assert_eq!(caption.text.as_deref(), Some("3 drawings"));
+    assert!(caption.children.is_empty());
+    assert_eq!(caption.get_attr("role"), None);
+}
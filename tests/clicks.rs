@@ -0,0 +1,111 @@
+use salt::testing::TestHarness;
+use salt::ui::{rect, AppCtx, Point};
+use salt::{App, Dimensions};
+
+const BUTTON: u64 = 1;
+
+#[derive(Default)]
+struct Clicks {
+    counts: Vec<u32>,
+    double_clicks: u32,
+    long_presses: Vec<Point>,
+}
+
+struct ClickApp {
+    ctx: AppCtx<Clicks>,
+    state: Clicks,
+}
+
+impl App for ClickApp {
+    type State = Clicks;
+
+    fn new() -> Self {
+        Self {
+            ctx: AppCtx::new(),
+            state: Clicks::default(),
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        self.ctx.view.push(
+            rect()
+                .width(100.0)
+                .height(100.0)
+                .finish(BUTTON)
+                .on_click_with(|state: &mut Clicks, event| {
+                    state.counts.push(event.info.click_count)
+                })
+                .on_double_click(|state: &mut Clicks| state.double_clicks += 1)
+                .on_long_press(|state: &mut Clicks, point| state.long_presses.push(point)),
+        );
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<Clicks>, &mut Clicks) {
+        (&mut self.ctx, &mut self.state)
+    }
+}
+
+fn harness() -> TestHarness<ClickApp> {
+    TestHarness::new(Dimensions {
+        width: 200,
+        height: 200,
+    })
+}
+
+#[test]
+fn rapid_clicks_count_up() {
+    let mut h = harness();
+    h.click(50.0, 50.0).click(50.0, 50.0).click(50.0, 50.0);
+    assert_eq!(h.state().counts, [1, 2, 3]);
+    assert_eq!(h.state().double_clicks, 1);
+}
+
+#[test]
+fn slow_clicks_start_over() {
+    let mut h = harness();
+    h.click(50.0, 50.0).advance(500.0).click(50.0, 50.0);
+    assert_eq!(h.state().counts, [1, 1]);
+    assert_eq!(h.state().double_clicks, 0);
+}
+
+#[test]
+fn distant_clicks_start_over() {
+    let mut h = harness();
+    h.click(50.0, 50.0).click(56.0, 50.0);
+    assert_eq!(h.state().counts, [1, 1]);
+
+    // Within the distance the sequence goes on
+    h.click(58.0, 52.0);
+    assert_eq!(h.state().counts, [1, 1, 2]);
+}
+
+#[test]
+fn long_press_fires_at_configured_delay() {
+    let mut h = harness();
+    h.app().ctx.gestures.config.long_press_delay = 300.0;
+    h.pointer_down(40.0, 60.0).advance(299.0);
+    assert!(h.state().long_presses.is_empty());
+
+    h.advance(1.0);
+    assert_eq!(h.state().long_presses, [Point::new(40.0, 60.0)]);
+}
+
+#[test]
+fn long_press_suppresses_click() {
+    let mut h = harness();
+    h.pointer_down(40.0, 60.0)
+        .advance(600.0)
+        .pointer_up(40.0, 60.0);
+    assert_eq!(h.state().long_presses.len(), 1);
+    assert!(h.state().counts.is_empty());
+}
+
+#[test]
+fn moving_away_rules_out_long_press() {
+    let mut h = harness();
+    h.pointer_down(40.0, 60.0)
+        .pointer_move(60.0, 60.0)
+        .advance(600.0)
+        .pointer_up(60.0, 60.0);
+    assert!(h.state().long_presses.is_empty());
+}
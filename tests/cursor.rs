@@ -0,0 +1,143 @@
+use salt::testing::TestHarness;
+use salt::ui::{rect, AppCtx, Cursor, DragPhase};
+use salt::{App, AppCore, Dimensions};
+
+const LINK: u64 = 1;
+const SPLITTER: u64 = 2;
+const CARD: u64 = 3;
+const TRACK: u64 = 4;
+const THUMB: u64 = 5;
+
+struct CursorApp {
+    ctx: AppCtx<()>,
+    state: (),
+}
+
+impl App for CursorApp {
+    type State = ();
+
+    fn new() -> Self {
+        Self {
+            ctx: AppCtx::new(),
+            state: (),
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        self.ctx.view.push(
+            rect()
+                .width(50.0)
+                .height(50.0)
+                .finish(LINK)
+                .cursor(Cursor::Pointer),
+        );
+        self.ctx.view.push(
+            rect()
+                .x(100.0)
+                .width(20.0)
+                .height(150.0)
+                .finish(SPLITTER)
+                .cursor(Cursor::ResizeEW)
+                .on_drag(|_: &mut (), _, _, _| {}),
+        );
+        self.ctx.view.push(
+            rect()
+                .x(200.0)
+                .width(50.0)
+                .height(50.0)
+                .finish(CARD)
+                .cursor(Cursor::Grab)
+                .on_drag(|_: &mut (), _, _, _| {}),
+        );
+
+        // Dragging the track moves the thumb, which takes the pointer capture
+        let capture = self.ctx.pointer_capture();
+        self.ctx.view.push(
+            rect()
+                .y(200.0)
+                .width(200.0)
+                .height(40.0)
+                .finish(TRACK)
+                .cursor(Cursor::Crosshair)
+                .on_drag(move |_: &mut (), phase, _, _| {
+                    if phase == DragPhase::Start {
+                        capture.capture(THUMB);
+                    }
+                }),
+        );
+        self.ctx.view.push(
+            rect()
+                .x(250.0)
+                .y(200.0)
+                .width(20.0)
+                .height(40.0)
+                .finish(THUMB)
+                .cursor(Cursor::Move),
+        );
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<()>, &mut ()) {
+        (&mut self.ctx, &mut self.state)
+    }
+}
+
+fn harness() -> TestHarness<CursorApp> {
+    TestHarness::new(Dimensions {
+        width: 300,
+        height: 300,
+    })
+}
+
+fn cursor(h: &mut TestHarness<CursorApp>) -> Cursor {
+    AppCore::cursor(h.app())
+}
+
+#[test]
+fn hovered_shape_sets_the_cursor() {
+    let mut h = harness();
+    assert_eq!(cursor(&mut h), Cursor::Default);
+
+    h.hover_shape(LINK);
+    assert_eq!(cursor(&mut h), Cursor::Pointer);
+    h.hover_shape(SPLITTER);
+    assert_eq!(cursor(&mut h), Cursor::ResizeEW);
+    h.hover(150.0, 100.0);
+    assert_eq!(cursor(&mut h), Cursor::Default);
+}
+
+#[test]
+fn dragged_shape_keeps_its_cursor_off_the_shape() {
+    let mut h = harness();
+    h.pointer_down(110.0, 20.0).pointer_move(20.0, 20.0);
+    assert_eq!(cursor(&mut h), Cursor::ResizeEW, "over the link");
+
+    h.pointer_up(20.0, 20.0);
+    assert_eq!(cursor(&mut h), Cursor::Pointer);
+}
+
+#[test]
+fn grab_cursor_turns_to_grabbing_while_dragged() {
+    let mut h = harness();
+    h.hover_shape(CARD);
+    assert_eq!(cursor(&mut h), Cursor::Grab);
+
+    h.pointer_down(220.0, 20.0).pointer_move(240.0, 100.0);
+    assert_eq!(cursor(&mut h), Cursor::Grabbing);
+    h.pointer_up(240.0, 100.0);
+    assert_eq!(cursor(&mut h), Cursor::Default);
+}
+
+#[test]
+fn captured_shape_cursor_wins_during_drag() {
+    let mut h = harness();
+    h.pointer_down(20.0, 220.0).pointer_move(60.0, 220.0);
+    assert_eq!(h.app().ctx.captured_id(), Some(THUMB));
+    assert_eq!(cursor(&mut h), Cursor::Move, "over the track");
+
+    h.pointer_move(20.0, 20.0);
+    assert_eq!(cursor(&mut h), Cursor::Move, "over the link");
+
+    // Hover follows the pointer again from the next move after release
+    h.pointer_up(20.0, 20.0).hover(20.0, 20.0);
+    assert_eq!(cursor(&mut h), Cursor::Pointer);
+}
@@ -0,0 +1,186 @@
+use salt::testing::TestHarness;
+use salt::ui::{rect, AppCtx, DragPhase, Point};
+use salt::{App, Dimensions, Event, EventType, MouseEvent};
+
+const HANDLE: u64 = 1;
+
+struct Drags {
+    phases: Vec<(DragPhase, Point, Point)>,
+    clicks: u32,
+    visible: bool,
+}
+
+struct DragApp {
+    ctx: AppCtx<Drags>,
+    state: Drags,
+}
+
+impl App for DragApp {
+    type State = Drags;
+
+    fn new() -> Self {
+        Self {
+            ctx: AppCtx::new(),
+            state: Drags {
+                phases: Vec::new(),
+                clicks: 0,
+                visible: true,
+            },
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        if !self.state.visible {
+            return;
+        }
+        self.ctx.view.push(
+            rect()
+                .width(100.0)
+                .height(100.0)
+                .finish(HANDLE)
+                .on_click(|state: &mut Drags| state.clicks += 1)
+                .on_drag(|state: &mut Drags, phase, start, current| {
+                    state.phases.push((phase, start, current))
+                }),
+        );
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<Drags>, &mut Drags) {
+        (&mut self.ctx, &mut self.state)
+    }
+}
+
+fn harness() -> TestHarness<DragApp> {
+    TestHarness::new(Dimensions {
+        width: 200,
+        height: 200,
+    })
+}
+
+fn phases(h: &mut TestHarness<DragApp>) -> Vec<DragPhase> {
+    h.state()
+        .phases
+        .iter()
+        .map(|&(phase, _, _)| phase)
+        .collect()
+}
+
+#[test]
+fn movement_within_slop_stays_a_click() {
+    let mut h = harness();
+    h.pointer_down(20.0, 20.0)
+        .pointer_move(22.0, 21.0)
+        .pointer_up(22.0, 21.0);
+    assert!(h.state().phases.is_empty());
+    assert_eq!(h.state().clicks, 1);
+}
+
+#[test]
+fn crossing_slop_starts_drag_from_press_point() {
+    let mut h = harness();
+    let origin = Point::new(20.0, 20.0);
+    h.pointer_down(20.0, 20.0)
+        .pointer_move(22.0, 20.0)
+        .pointer_move(30.0, 20.0);
+    assert_eq!(
+        h.state().phases,
+        [
+            (DragPhase::Start, origin, origin),
+            (DragPhase::Move, origin, Point::new(30.0, 20.0)),
+        ]
+    );
+
+    h.pointer_up(40.0, 25.0);
+    assert_eq!(
+        h.state().phases.last(),
+        Some(&(DragPhase::End, origin, Point::new(40.0, 25.0)))
+    );
+    assert_eq!(h.state().clicks, 0);
+}
+
+#[test]
+fn drag_returning_to_press_point_is_no_click() {
+    let mut h = harness();
+    h.pointer_down(20.0, 20.0)
+        .pointer_move(40.0, 20.0)
+        .pointer_move(20.0, 20.0)
+        .pointer_up(20.0, 20.0);
+    assert_eq!(
+        phases(&mut h),
+        [
+            DragPhase::Start,
+            DragPhase::Move,
+            DragPhase::Move,
+            DragPhase::End
+        ]
+    );
+    assert_eq!(h.state().clicks, 0);
+}
+
+/// Press on the handle and drag it past the slop
+fn start_drag(h: &mut TestHarness<DragApp>) {
+    h.pointer_down(20.0, 20.0).pointer_move(40.0, 20.0);
+    assert_eq!(phases(h), [DragPhase::Start, DragPhase::Move]);
+}
+
+/// Release where the press began, which would be a click had the drag not been cancelled
+fn release(h: &mut TestHarness<DragApp>) {
+    h.pointer_move(20.0, 20.0).pointer_up(20.0, 20.0);
+}
+
+#[test]
+fn blur_cancels_drag() {
+    let mut h = harness();
+    start_drag(&mut h);
+    h.send(Event::Blur);
+    release(&mut h);
+    assert_eq!(
+        phases(&mut h),
+        [DragPhase::Start, DragPhase::Move, DragPhase::Cancel]
+    );
+    assert_eq!(h.state().clicks, 0);
+}
+
+#[test]
+fn escape_cancels_drag() {
+    let mut h = harness();
+    start_drag(&mut h);
+    h.press_key("Escape");
+    release(&mut h);
+    assert_eq!(
+        phases(&mut h),
+        [DragPhase::Start, DragPhase::Move, DragPhase::Cancel]
+    );
+    assert_eq!(h.state().clicks, 0);
+}
+
+#[test]
+fn pointer_cancel_cancels_drag() {
+    let mut h = harness();
+    start_drag(&mut h);
+    h.send(MouseEvent::new(EventType::PointerCancel, 40.0, 20.0));
+    release(&mut h);
+    assert_eq!(
+        phases(&mut h),
+        [DragPhase::Start, DragPhase::Move, DragPhase::Cancel]
+    );
+    assert_eq!(h.state().clicks, 0);
+}
+
+#[test]
+fn removing_the_shape_cancels_drag() {
+    let mut h = harness();
+    start_drag(&mut h);
+    h.state().visible = false;
+    h.render();
+    assert_eq!(
+        phases(&mut h),
+        [DragPhase::Start, DragPhase::Move, DragPhase::Cancel]
+    );
+
+    h.state().visible = true;
+    h.render();
+    release(&mut h);
+    assert_eq!(phases(&mut h).len(), 3);
+    assert_eq!(h.state().clicks, 0);
+}
@@ -0,0 +1,26 @@
+use salt::EventType;
+
+#[test]
+fn entry_points_reject_other_kinds_of_events() {
+    assert_eq!(
+        EventType::parse_pointer("pointerdown"),
+        Ok(EventType::PointerDown)
+    );
+    assert_eq!(EventType::parse_key("keyup"), Ok(EventType::KeyUp));
+    assert_eq!(EventType::parse_text("paste"), Ok(EventType::Paste));
+
+    let err = EventType::parse_key("pointerdown").unwrap_err();
+    assert_eq!(err.to_string(), "`pointerdown` is not a keyboard event");
+    let err = EventType::parse_pointer("keydown").unwrap_err();
+    assert_eq!(err.to_string(), "`keydown` is not a mouse or pointer event");
+    assert!(EventType::parse_pointer("paste").is_err());
+    assert!(EventType::parse_pointer("wheel").is_err());
+    assert!(EventType::parse_text("keydown").is_err());
+}
+
+#[test]
+fn unknown_event_types_are_rejected() {
+    let err = EventType::parse_text("scroll").unwrap_err();
+    assert_eq!(err.expected, None);
+    assert_eq!(err.to_string(), "unknown event type `scroll`");
+}
@@ -0,0 +1,85 @@
+use salt::testing::TestHarness;
+use salt::ui::{rect, AppCtx};
+use salt::{App, AppCore, Dimensions, Modifiers};
+
+struct FormApp {
+    ctx: AppCtx<()>,
+    state: (),
+}
+
+impl App for FormApp {
+    type State = ();
+
+    fn new() -> Self {
+        Self {
+            ctx: AppCtx::new(),
+            state: (),
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        for id in 1..=3 {
+            self.ctx.view.push(
+                rect()
+                    .x(id as f32 * 50.0)
+                    .width(40.0)
+                    .height(40.0)
+                    .finish(id)
+                    .focusable(true),
+            );
+        }
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<()>, &mut ()) {
+        (&mut self.ctx, &mut self.state)
+    }
+}
+
+fn harness() -> TestHarness<FormApp> {
+    TestHarness::new(Dimensions {
+        width: 200,
+        height: 50,
+    })
+}
+
+#[test]
+fn tab_walks_shapes_then_leaves_the_app() {
+    let mut h = harness();
+    for id in 1..=3 {
+        h.key_down("Tab");
+        assert_eq!(h.focused(), Some(id));
+        assert!(h.app().default_prevented());
+    }
+
+    // Past the last shape the browser moves focus on
+    h.key_down("Tab");
+    assert_eq!(h.focused(), None);
+    assert!(!h.app().default_prevented());
+}
+
+#[test]
+fn shift_tab_leaves_from_the_first_shape() {
+    let mut h = harness();
+    h.with_modifiers(Modifiers {
+        shift: true,
+        ..Modifiers::default()
+    });
+    h.key_down("Tab");
+    assert_eq!(h.focused(), Some(3));
+
+    h.key_down("Tab").key_down("Tab").key_down("Tab");
+    assert_eq!(h.focused(), None);
+    assert!(!h.app().default_prevented());
+}
+
+#[test]
+fn focus_moving_out_of_the_app_clears_focus() {
+    let mut h = harness();
+    h.key_down("Tab").key_down("Tab");
+    assert_eq!(h.focused(), Some(2));
+    assert!(h.svg().contains("salt-focus-ring"));
+
+    h.focus_out();
+    assert_eq!(h.focused(), None);
+    assert!(!h.svg().contains("salt-focus-ring"));
+}
@@ -0,0 +1,187 @@
+use std::time::Duration;
+
+use salt::testing::TestHarness;
+use salt::ui::{rect, AppCtx, DragPhase, Point};
+use salt::{App, Dimensions};
+
+const BUTTON: u64 = 1;
+const HANDLE: u64 = 2;
+const PRESS: u64 = 3;
+const FIELD: u64 = 4;
+
+struct Demo {
+    clicks: u32,
+    handle: Point,
+    drag_phases: Vec<DragPhase>,
+    long_pressed: bool,
+    keys: Vec<String>,
+    ticks: u32,
+    frames: u32,
+}
+
+struct DemoApp {
+    ctx: AppCtx<Demo>,
+    state: Demo,
+}
+
+impl App for DemoApp {
+    type State = Demo;
+
+    fn new() -> Self {
+        let ctx = AppCtx::new();
+        ctx.set_interval(Duration::from_secs(1), |state: &mut Demo| state.ticks += 1);
+        Self {
+            ctx,
+            state: Demo {
+                clicks: 0,
+                handle: Point::new(200.0, 0.0),
+                drag_phases: Vec::new(),
+                long_pressed: false,
+                keys: Vec::new(),
+                ticks: 0,
+                frames: 0,
+            },
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        self.ctx.view.push(
+            rect()
+                .width(100.0)
+                .height(40.0)
+                .finish(BUTTON)
+                .on_click(|state: &mut Demo| state.clicks += 1),
+        );
+        self.ctx.view.push(
+            rect()
+                .x(self.state.handle.x)
+                .y(self.state.handle.y)
+                .width(40.0)
+                .height(40.0)
+                .finish(HANDLE)
+                .on_drag(|state: &mut Demo, phase, start, current| {
+                    state.drag_phases.push(phase);
+                    if phase == DragPhase::End {
+                        state.handle.x += current.x - start.x;
+                        state.handle.y += current.y - start.y;
+                    }
+                }),
+        );
+        self.ctx.view.push(
+            rect()
+                .y(100.0)
+                .width(40.0)
+                .height(40.0)
+                .finish(PRESS)
+                .on_long_press(|state: &mut Demo, _| state.long_pressed = true),
+        );
+        self.ctx.view.push(
+            rect()
+                .y(200.0)
+                .width(100.0)
+                .height(40.0)
+                .finish(FIELD)
+                .focusable(true)
+                .on_key(|state: &mut Demo, event| {
+                    state.keys.push(event.key.clone());
+                    true
+                }),
+        );
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<Demo>, &mut Demo) {
+        (&mut self.ctx, &mut self.state)
+    }
+
+    fn tick(&mut self, _dt: f64) {
+        self.state.frames += 1;
+    }
+}
+
+fn harness() -> TestHarness<DemoApp> {
+    TestHarness::new(Dimensions {
+        width: 400,
+        height: 300,
+    })
+}
+
+#[test]
+fn click_runs_on_click() {
+    let mut h = harness();
+    h.click_shape(BUTTON);
+    assert_eq!(h.state().clicks, 1);
+
+    // Releasing away from the press is not a click
+    h.pointer_down(10.0, 10.0).pointer_up(300.0, 280.0);
+    assert_eq!(h.state().clicks, 1);
+}
+
+#[test]
+fn drag_moves_handle() {
+    let mut h = harness();
+    h.drag_shape(HANDLE, Point::new(270.0, 70.0), 5);
+    assert_eq!(
+        h.state().drag_phases.first(),
+        Some(&DragPhase::Start),
+        "drag starts once the pointer leaves the slop"
+    );
+    assert_eq!(h.state().drag_phases.last(), Some(&DragPhase::End));
+    assert_eq!(h.state().handle, Point::new(250.0, 50.0));
+    assert_eq!(h.state().clicks, 0);
+}
+
+#[test]
+fn keys_reach_focused_shape() {
+    let mut h = harness();
+    h.press_key("a");
+    assert!(h.state().keys.is_empty());
+
+    h.click_shape(FIELD);
+    assert_eq!(h.focused(), Some(FIELD));
+    h.type_text("hi");
+    assert_eq!(h.state().keys, ["h", "h", "i", "i"]);
+}
+
+#[test]
+fn long_press_fires_after_delay() {
+    let mut h = harness();
+    h.pointer_down(20.0, 120.0).advance(499.0);
+    assert!(!h.state().long_pressed);
+    h.advance(1.0);
+    assert!(h.state().long_pressed);
+}
+
+#[test]
+fn long_press_after_fractional_frames() {
+    let mut h = harness();
+    for _ in 0..4 {
+        h.frame(16.7);
+    }
+    h.pointer_down(20.0, 120.0).advance(1000.0);
+    assert!(h.state().long_pressed);
+}
+
+#[test]
+fn virtual_time_drives_timers() {
+    let mut h = harness();
+    h.advance(999.0);
+    assert_eq!(h.state().ticks, 0);
+    h.advance(1.0);
+    assert_eq!(h.state().ticks, 1);
+
+    // Each period passed over fires in turn
+    h.advance(3000.0);
+    assert_eq!(h.state().ticks, 4);
+    assert_eq!(h.now(), 4000.0);
+}
+
+#[test]
+fn frames_tick_only_when_requested() {
+    let mut h = harness();
+    h.frame(16.0);
+    assert_eq!(h.state().frames, 0);
+
+    h.app().ctx.request_animation_frame();
+    h.frame(16.0).frame(16.0);
+    assert_eq!(h.state().frames, 1);
+}
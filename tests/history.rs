@@ -0,0 +1,206 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use salt::testing::TestHarness;
+use salt::ui::{rect, AppCtx, HistoryStatus, Point};
+use salt::{App, Dimensions};
+
+const INCREMENT: u64 = 1;
+const MIX: u64 = 2;
+const HANDLE: u64 = 3;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Doc {
+    value: i32,
+    points: Vec<Point>,
+}
+
+struct EditorApp {
+    ctx: AppCtx<Doc>,
+    state: Doc,
+}
+
+impl App for EditorApp {
+    type State = Doc;
+
+    fn new() -> Self {
+        Self {
+            ctx: AppCtx::new(),
+            state: Doc::default(),
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        let history = self.ctx.history();
+        self.ctx
+            .view
+            .push(rect().width(50.0).height(50.0).finish(INCREMENT).on_click(
+                move |doc: &mut Doc| {
+                    history.checkpoint(doc);
+                    doc.value += 1;
+                },
+            ));
+
+        let history = self.ctx.history();
+        self.ctx.view.push(
+            rect()
+                .x(50.0)
+                .width(50.0)
+                .height(50.0)
+                .finish(MIX)
+                .on_click(move |doc: &mut Doc| {
+                    history.checkpoint(doc);
+                    doc.value += 10;
+                    history.execute(doc, |doc| doc.value *= 2, |doc| doc.value /= 2);
+                }),
+        );
+
+        let history = self.ctx.history();
+        let keys = self.ctx.history();
+        self.ctx.view.push(
+            rect()
+                .y(100.0)
+                .width(100.0)
+                .height(100.0)
+                .finish(HANDLE)
+                .on_drag(move |doc: &mut Doc, _phase, _start, current| {
+                    history.checkpoint(doc);
+                    doc.points.push(current);
+                })
+                .on_key(move |doc: &mut Doc, event| event.key == "z" && keys.undo(doc)),
+        );
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<Doc>, &mut Doc) {
+        (&mut self.ctx, &mut self.state)
+    }
+}
+
+fn harness() -> TestHarness<EditorApp> {
+    TestHarness::new(Dimensions {
+        width: 300,
+        height: 300,
+    })
+}
+
+fn undo(h: &mut TestHarness<EditorApp>) -> bool {
+    let app = h.app();
+    app.ctx.history.undo(&mut app.state)
+}
+
+fn redo(h: &mut TestHarness<EditorApp>) -> bool {
+    let app = h.app();
+    app.ctx.history.redo(&mut app.state)
+}
+
+fn status(h: &mut TestHarness<EditorApp>) -> HistoryStatus {
+    h.app().ctx.history.status()
+}
+
+#[test]
+fn checkpoints_undo_and_redo() {
+    let mut h = harness();
+    let notified = Rc::new(Cell::new(0));
+    let count = notified.clone();
+    h.app()
+        .ctx
+        .history
+        .on_change(move |_| count.set(count.get() + 1));
+
+    h.click_shape(INCREMENT).click_shape(INCREMENT);
+    assert_eq!(h.state().value, 2);
+    assert_eq!(
+        status(&mut h),
+        HistoryStatus {
+            undo_steps: 2,
+            redo_steps: 0
+        }
+    );
+
+    assert!(undo(&mut h));
+    assert_eq!(h.state().value, 1);
+    assert!(redo(&mut h));
+    assert_eq!(h.state().value, 2);
+    assert!(undo(&mut h) && undo(&mut h));
+    assert_eq!(h.state().value, 0);
+    assert!(!undo(&mut h));
+    assert_eq!(notified.get(), 6);
+
+    // A new change forgets what could be redone
+    h.click_shape(INCREMENT);
+    assert!(!redo(&mut h));
+    assert_eq!(h.state().value, 1);
+}
+
+#[test]
+fn drag_is_one_step() {
+    let mut h = harness();
+    h.drag(Point::new(20.0, 120.0), Point::new(80.0, 180.0), 5);
+    assert_eq!(h.state().points.len(), 7);
+    assert_eq!(status(&mut h).undo_steps, 1);
+
+    assert!(undo(&mut h));
+    assert_eq!(h.state(), &Doc::default());
+    assert!(redo(&mut h));
+    assert_eq!(h.state().points.len(), 7);
+}
+
+#[test]
+fn undo_during_drag_keeps_the_gesture_grouped() {
+    let mut h = harness();
+    h.pointer_down(20.0, 120.0)
+        .pointer_move(40.0, 120.0)
+        .pointer_move(60.0, 120.0);
+    assert_eq!(h.focused(), Some(HANDLE));
+
+    h.press_key("z");
+    assert_eq!(h.state(), &Doc::default());
+
+    h.pointer_move(70.0, 130.0)
+        .pointer_move(80.0, 140.0)
+        .pointer_up(80.0, 140.0);
+    assert_eq!(h.state().points.len(), 3);
+    assert_eq!(
+        status(&mut h),
+        HistoryStatus {
+            undo_steps: 1,
+            redo_steps: 0
+        },
+        "the rest of the drag is one step"
+    );
+
+    // Groups are balanced again, so a click commits right away
+    h.click_shape(INCREMENT);
+    assert_eq!(status(&mut h).undo_steps, 2);
+    assert!(undo(&mut h) && undo(&mut h));
+    assert_eq!(h.state(), &Doc::default());
+}
+
+#[test]
+fn commands_and_snapshots_in_one_step() {
+    let mut h = harness();
+    h.click_shape(INCREMENT).click_shape(MIX);
+    assert_eq!(h.state().value, 22);
+    assert_eq!(status(&mut h).undo_steps, 2);
+
+    assert!(undo(&mut h));
+    assert_eq!(h.state().value, 1);
+    assert!(redo(&mut h));
+    assert_eq!(h.state().value, 22);
+    assert!(undo(&mut h) && undo(&mut h));
+    assert_eq!(h.state().value, 0);
+}
+
+#[test]
+fn step_limit_drops_the_oldest_steps() {
+    let mut h = harness();
+    h.app().ctx.history.set_step_limit(3);
+    for _ in 0..5 {
+        h.click_shape(INCREMENT);
+    }
+    assert_eq!(status(&mut h).undo_steps, 3);
+
+    while undo(&mut h) {}
+    assert_eq!(h.state().value, 2);
+    assert_eq!(status(&mut h).redo_steps, 3);
+}
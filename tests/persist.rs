@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+
+use salt::persist::{MemoryStorage, PersistConfig};
+use salt::testing::TestHarness;
+use salt::ui::{rect, AppCtx, DragPhase};
+use salt::{App, Dimensions};
+
+const HANDLE: u64 = 1;
+const KEY: &str = "demo";
+
+#[derive(Default, Serialize, Deserialize)]
+struct Demo {
+    frames: u32,
+    cancelled: u32,
+}
+
+struct DemoApp {
+    ctx: AppCtx<Demo>,
+    state: Demo,
+}
+
+impl DemoApp {
+    fn with_storage(storage: MemoryStorage) -> Self {
+        let mut app = Self {
+            ctx: AppCtx::new(),
+            state: Demo::default(),
+        };
+        app.ctx
+            .persist(&mut app.state, PersistConfig::new(KEY, storage))
+            .unwrap();
+        app
+    }
+}
+
+impl App for DemoApp {
+    type State = Demo;
+
+    fn new() -> Self {
+        Self::with_storage(MemoryStorage::new())
+    }
+
+    fn view(&mut self, dimensions: Dimensions) {
+        // The handle only fits in wide layouts
+        if dimensions.width >= 300 {
+            self.ctx
+                .view
+                .push(rect().width(40.0).height(40.0).finish(HANDLE).on_drag(
+                    |state: &mut Demo, phase, _, _| {
+                        if phase == DragPhase::Cancel {
+                            state.cancelled += 1;
+                        }
+                    },
+                ));
+        }
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<Demo>, &mut Demo) {
+        (&mut self.ctx, &mut self.state)
+    }
+
+    fn tick(&mut self, _dt: f64) {
+        self.state.frames += 1;
+    }
+}
+
+fn stored(storage: &MemoryStorage) -> Option<Demo> {
+    PersistConfig::new(KEY, storage.clone()).load().unwrap()
+}
+
+#[test]
+fn tick_changes_are_saved() {
+    let storage = MemoryStorage::new();
+    let mut h = TestHarness::with_app(
+        DemoApp::with_storage(storage.clone()),
+        Dimensions {
+            width: 400,
+            height: 200,
+        },
+    );
+    h.app().ctx.request_animation_frame();
+    h.frame(16.0).advance(1000.0);
+    assert_eq!(stored(&storage).map(|demo| demo.frames), Some(1));
+}
+
+#[test]
+fn drags_cancelled_by_render_are_saved() {
+    let storage = MemoryStorage::new();
+    let mut h = TestHarness::with_app(
+        DemoApp::with_storage(storage.clone()),
+        Dimensions {
+            width: 400,
+            height: 200,
+        },
+    );
+    h.pointer_down(20.0, 20.0)
+        .pointer_move(30.0, 20.0)
+        .advance(1000.0);
+    h.resize(Dimensions {
+        width: 200,
+        height: 200,
+    });
+    assert_eq!(h.state().cancelled, 1);
+
+    h.advance(1000.0);
+    assert_eq!(stored(&storage).map(|demo| demo.cancelled), Some(1));
+    assert!(h.shape(HANDLE).is_none());
+}
+
+#[test]
+fn continuous_animation_is_saved() {
+    let storage = MemoryStorage::new();
+    let mut h = TestHarness::with_app(
+        DemoApp::with_storage(storage.clone()),
+        Dimensions {
+            width: 400,
+            height: 200,
+        },
+    );
+    // Every frame changes the state well within the save delay
+    for _ in 0..150 {
+        h.app().ctx.request_animation_frame();
+        h.frame(16.0);
+    }
+    let saved = stored(&storage).map(|demo| demo.frames);
+    assert!(
+        saved.is_some_and(|frames| frames < 150),
+        "saved {saved:?} frames while animating"
+    );
+}
@@ -0,0 +1,99 @@
+use salt::testing::TestHarness;
+use salt::ui::{rect, AppCtx, DragPhase};
+use salt::{App, Dimensions};
+
+const TRACK: u64 = 1;
+const THUMB: u64 = 2;
+
+#[derive(Default)]
+struct Slider {
+    value: f32,
+    thumb_hovered: bool,
+    track_hovered: bool,
+}
+
+struct SliderApp {
+    ctx: AppCtx<Slider>,
+    state: Slider,
+}
+
+impl App for SliderApp {
+    type State = Slider;
+
+    fn new() -> Self {
+        Self {
+            ctx: AppCtx::new(),
+            state: Slider::default(),
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        self.ctx.view.push(
+            rect()
+                .x(0.0)
+                .y(0.0)
+                .width(400.0)
+                .height(200.0)
+                .finish(TRACK)
+                .on_hover(|state: &mut Slider, hovered, _| state.track_hovered = hovered),
+        );
+
+        // The thumb captures the pointer once dragged, so it keeps the drag and the
+        // hover while the pointer strays off it
+        let capture = self.ctx.pointer_capture();
+        self.ctx.view.push(
+            rect()
+                .x(self.state.value)
+                .y(40.0)
+                .width(20.0)
+                .height(20.0)
+                .finish(THUMB)
+                .on_hover(|state: &mut Slider, hovered, _| state.thumb_hovered = hovered)
+                .on_drag(move |state: &mut Slider, phase, _, current| {
+                    if phase == DragPhase::Start {
+                        capture.capture(THUMB);
+                    }
+                    state.value = current.x.clamp(0.0, 380.0);
+                }),
+        );
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<Slider>, &mut Slider) {
+        (&mut self.ctx, &mut self.state)
+    }
+}
+
+fn harness() -> TestHarness<SliderApp> {
+    TestHarness::new(Dimensions {
+        width: 400,
+        height: 200,
+    })
+}
+
+#[test]
+fn drag_callback_captures_pointer() {
+    let mut h = harness();
+    h.pointer_move(10.0, 50.0).pointer_down(10.0, 50.0);
+    assert!(h.state().thumb_hovered);
+    assert_eq!(h.app().ctx.captured_id(), None);
+
+    h.pointer_move(18.0, 50.0);
+    assert_eq!(h.app().ctx.captured_id(), Some(THUMB));
+
+    // Off the thumb and over the track, the thumb still gets the pointer
+    h.pointer_move(150.0, 150.0);
+    assert!(h.state().thumb_hovered);
+    assert!(!h.state().track_hovered);
+    assert_eq!(h.state().value, 150.0);
+
+    h.pointer_up(150.0, 150.0);
+    assert_eq!(h.app().ctx.captured_id(), None);
+}
+
+#[test]
+fn hover_follows_pointer_without_capture() {
+    let mut h = harness();
+    h.pointer_move(10.0, 50.0).pointer_move(150.0, 150.0);
+    assert!(!h.state().thumb_hovered);
+    assert!(h.state().track_hovered);
+}
@@ -0,0 +1,151 @@
+use salt::testing::TestHarness;
+use salt::ui::{rect, AppCtx, EventCtx, EventPhase};
+use salt::{App, Dimensions};
+
+const CARD: u64 = 1;
+const ROW: u64 = 2;
+const BUTTON: u64 = 3;
+
+#[derive(Default)]
+struct Log {
+    /// Shape, phase and target of each click callback run
+    calls: Vec<(u64, EventPhase, u64)>,
+    /// Where a callback stops propagation
+    stop_at: Option<(u64, EventPhase)>,
+}
+
+struct NestedApp {
+    ctx: AppCtx<Log>,
+    state: Log,
+}
+
+impl App for NestedApp {
+    type State = Log;
+
+    fn new() -> Self {
+        Self {
+            ctx: AppCtx::new(),
+            state: Log::default(),
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        let shapes = [
+            (CARD, None, 200.0),
+            (ROW, Some(CARD), 100.0),
+            (BUTTON, Some(ROW), 50.0),
+        ];
+        for (id, parent, size) in shapes {
+            let mut shape = rect()
+                .width(size)
+                .height(size)
+                .finish(id)
+                .on_click_with(log_click)
+                .on_click_capture(log_click);
+            if let Some(parent) = parent {
+                shape = shape.parent(parent);
+            }
+            self.ctx.view.push(shape);
+        }
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<Log>, &mut Log) {
+        (&mut self.ctx, &mut self.state)
+    }
+}
+
+fn log_click(log: &mut Log, event: &mut EventCtx) {
+    log.calls
+        .push((event.current_id, event.phase, event.target_id));
+    if log.stop_at == Some((event.current_id, event.phase)) {
+        event.stop_propagation();
+    }
+}
+
+fn harness() -> TestHarness<NestedApp> {
+    TestHarness::new(Dimensions {
+        width: 300,
+        height: 300,
+    })
+}
+
+fn calls(h: &mut TestHarness<NestedApp>) -> Vec<(u64, EventPhase)> {
+    h.state()
+        .calls
+        .iter()
+        .map(|&(id, phase, _)| (id, phase))
+        .collect()
+}
+
+#[test]
+fn click_captures_down_and_bubbles_up() {
+    let mut h = harness();
+    h.click(20.0, 20.0);
+    assert_eq!(
+        calls(&mut h),
+        [
+            (CARD, EventPhase::Capture),
+            (ROW, EventPhase::Capture),
+            (BUTTON, EventPhase::Target),
+            (BUTTON, EventPhase::Target),
+            (ROW, EventPhase::Bubble),
+            (CARD, EventPhase::Bubble),
+        ]
+    );
+    assert!(h
+        .state()
+        .calls
+        .iter()
+        .all(|&(_, _, target)| target == BUTTON));
+}
+
+#[test]
+fn click_on_parent_skips_children() {
+    let mut h = harness();
+    h.click(80.0, 80.0);
+    assert_eq!(
+        calls(&mut h),
+        [
+            (CARD, EventPhase::Capture),
+            (ROW, EventPhase::Target),
+            (ROW, EventPhase::Target),
+            (CARD, EventPhase::Bubble),
+        ]
+    );
+}
+
+#[test]
+fn stop_propagation_while_bubbling() {
+    let mut h = harness();
+    h.state().stop_at = Some((ROW, EventPhase::Bubble));
+    h.click(20.0, 20.0);
+    assert_eq!(
+        calls(&mut h).last(),
+        Some(&(ROW, EventPhase::Bubble)),
+        "the card never sees the click"
+    );
+    assert_eq!(calls(&mut h).len(), 5);
+}
+
+#[test]
+fn stop_propagation_while_capturing() {
+    let mut h = harness();
+    h.state().stop_at = Some((CARD, EventPhase::Capture));
+    h.click(20.0, 20.0);
+    assert_eq!(calls(&mut h), [(CARD, EventPhase::Capture)]);
+}
+
+#[test]
+fn stop_propagation_at_target() {
+    let mut h = harness();
+    h.state().stop_at = Some((BUTTON, EventPhase::Target));
+    h.click(20.0, 20.0);
+    assert_eq!(
+        calls(&mut h),
+        [
+            (CARD, EventPhase::Capture),
+            (ROW, EventPhase::Capture),
+            (BUTTON, EventPhase::Target),
+        ]
+    );
+}
@@ -0,0 +1,123 @@
+use salt::recording::{RecordedInput, Recording};
+use salt::testing::TestHarness;
+use salt::ui::{rect, AppCtx, DragPhase, Point};
+use salt::{App, Dimensions};
+
+const BUTTON: u64 = 1;
+const KNOB: u64 = 2;
+
+#[derive(Debug, PartialEq)]
+struct Board {
+    clicks: u32,
+    knob: Point,
+    frames: u32,
+}
+
+struct BoardApp {
+    ctx: AppCtx<Board>,
+    state: Board,
+}
+
+impl App for BoardApp {
+    type State = Board;
+
+    fn new() -> Self {
+        Self {
+            ctx: AppCtx::new(),
+            state: Board {
+                clicks: 0,
+                knob: Point::new(100.0, 100.0),
+                frames: 0,
+            },
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        self.ctx.view.push(
+            rect()
+                .width(50.0)
+                .height(50.0)
+                .finish(BUTTON)
+                .on_click(|state: &mut Board| state.clicks += 1),
+        );
+        self.ctx.view.push(
+            rect()
+                .x(self.state.knob.x)
+                .y(self.state.knob.y)
+                .width(20.0)
+                .height(20.0)
+                .finish(KNOB)
+                .on_drag(|state: &mut Board, phase, _start, current| {
+                    if phase != DragPhase::Cancel {
+                        state.knob = current;
+                    }
+                }),
+        );
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<Board>, &mut Board) {
+        (&mut self.ctx, &mut self.state)
+    }
+
+    fn tick(&mut self, _dt: f64) {
+        self.state.frames += 1;
+    }
+}
+
+fn harness() -> TestHarness<BoardApp> {
+    TestHarness::new(Dimensions {
+        width: 300,
+        height: 300,
+    })
+}
+
+/// Record a session that clicks, drags the knob and then grabs it at its new place
+fn record_session() -> (Recording, TestHarness<BoardApp>) {
+    let mut h = harness();
+    h.app().ctx.start_recording();
+    h.click_shape(BUTTON)
+        .click_shape(BUTTON)
+        .drag_shape(KNOB, Point::new(200.0, 150.0), 4)
+        .frame(16.0)
+        .advance(100.0)
+        .drag(Point::new(205.0, 155.0), Point::new(250.0, 250.0), 3)
+        .click(10.0, 10.0);
+    let recording = h.app().ctx.stop_recording().expect("recording was started");
+    (recording, h)
+}
+
+#[test]
+fn recording_starts_with_a_render() {
+    let mut h = harness();
+    h.app().ctx.start_recording();
+    let recording = h.app().ctx.stop_recording().unwrap();
+    assert!(matches!(
+        recording.entries.as_slice(),
+        [RecordedInput::Render(Dimensions {
+            width: 300,
+            height: 300
+        })]
+    ));
+}
+
+#[test]
+fn replay_reproduces_the_live_session() {
+    let (recording, mut live) = record_session();
+    assert_eq!(live.state().clicks, 3);
+    assert_eq!(live.state().knob, Point::new(250.0, 250.0));
+
+    let mut replayed = BoardApp::new();
+    recording.replay(&mut replayed);
+    assert_eq!(&replayed.state, live.state());
+}
+
+#[test]
+fn replay_from_json_reproduces_the_live_session() {
+    let (recording, mut live) = record_session();
+    let recording = Recording::from_json(&recording.to_json()).unwrap();
+
+    let mut replayed = BoardApp::new();
+    let svg = recording.replay_until(&mut replayed, recording.len());
+    assert_eq!(&replayed.state, live.state());
+    assert!(svg.is_some());
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use salt::ui::Scheduler;
+
+#[test]
+fn interval_skips_missed_periods() {
+    let scheduler = Scheduler::<u32>::new();
+    scheduler.set_interval(Duration::from_millis(100), |count| *count += 1);
+
+    let mut count = 0;
+    assert!(scheduler.run_due(&mut count, 1050.0));
+    assert_eq!(count, 1);
+    assert_eq!(scheduler.next_deadline(), Some(1100.0));
+
+    assert!(scheduler.run_due(&mut count, 1100.0));
+    assert_eq!(count, 2);
+    assert_eq!(scheduler.next_deadline(), Some(1200.0));
+}
+
+#[test]
+fn timers_run_in_due_order() {
+    let scheduler = Scheduler::<Vec<&str>>::new();
+    scheduler.set_timeout(Duration::from_millis(30), |log| log.push("late"));
+    scheduler.set_timeout(Duration::from_millis(10), |log| log.push("early"));
+
+    let mut log = Vec::new();
+    assert!(!scheduler.run_due(&mut log, 5.0));
+    scheduler.run_due(&mut log, 50.0);
+    assert_eq!(log, ["early", "late"]);
+    assert_eq!(scheduler.next_deadline(), None);
+}
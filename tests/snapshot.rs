@@ -0,0 +1,86 @@
+use salt::testing::{snapshot_path, TestHarness, SNAPSHOT_PRECISION};
+use salt::ui::{rect, text, AppCtx, Color, SvgElement};
+use salt::{App, Dimensions};
+
+const BUTTON: u64 = 1;
+const LABEL: u64 = 2;
+
+struct CounterApp {
+    ctx: AppCtx<u32>,
+    count: u32,
+}
+
+impl App for CounterApp {
+    type State = u32;
+
+    fn new() -> Self {
+        Self {
+            ctx: AppCtx::new(),
+            count: 0,
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        self.ctx.view.push(
+            rect()
+                .x(20.0)
+                .y(20.0)
+                .width(120.0)
+                .height(40.0)
+                .fill(Color::new([0.4, 0.6, 1.0, 1.0]))
+                .finish(BUTTON)
+                .on_click(|count: &mut u32| *count += 1),
+        );
+        self.ctx.view.push(
+            text()
+                .x(20.0)
+                .y(100.0)
+                .fill(Color::BLACK)
+                .text(format!("Count: {}", self.count))
+                .finish(LABEL),
+        );
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<u32>, &mut u32) {
+        (&mut self.ctx, &mut self.count)
+    }
+}
+
+fn harness() -> TestHarness<CounterApp> {
+    TestHarness::new(Dimensions {
+        width: 200,
+        height: 120,
+    })
+}
+
+fn stored(name: &str) -> SvgElement {
+    let path = snapshot_path(name);
+    let text = std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("cannot read {}: {}", path.display(), err));
+    SvgElement::parse(&text).expect("stored snapshot parses")
+}
+
+#[test]
+fn counter_matches_snapshot() {
+    harness().assert_snapshot("counter");
+}
+
+#[test]
+fn snapshot_round_trips_through_parse() {
+    let h = harness();
+    let rendered = h.svg_tree().normalized(SNAPSHOT_PRECISION);
+    let reparsed = SvgElement::parse(&rendered.to_pretty_string(SNAPSHOT_PRECISION)).unwrap();
+    assert!(reparsed.diff(&rendered).is_empty());
+    assert_eq!(reparsed, rendered);
+}
+
+#[test]
+fn changed_render_shows_structural_diff() {
+    let mut h = harness();
+    h.click_shape(BUTTON);
+    let changes = stored("counter").diff(&h.svg_tree().normalized(SNAPSHOT_PRECISION));
+    assert_eq!(
+        changes,
+        ["svg > text[1]: text \"Count: 0\" changed to \"Count: 1\""]
+    );
+}
@@ -0,0 +1,101 @@
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use salt::testing::TestHarness;
+use salt::ui::{rect, AppCtx};
+use salt::{App, Dimensions};
+
+const LOAD: u64 = 1;
+
+/// Future that stays pending until a value is delivered through the shared cell
+struct Response(Rc<Cell<Option<u32>>>);
+
+impl Future for Response {
+    type Output = u32;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<u32> {
+        match self.0.take() {
+            Some(value) => Poll::Ready(value),
+            None => Poll::Pending,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Loader {
+    loaded: Option<u32>,
+    doubled: Option<u32>,
+}
+
+struct LoaderApp {
+    ctx: AppCtx<Loader>,
+    state: Loader,
+    response: Rc<Cell<Option<u32>>>,
+}
+
+impl App for LoaderApp {
+    type State = Loader;
+
+    fn new() -> Self {
+        let ctx = AppCtx::new();
+        ctx.spawn(async { 21 * 2 }, |state: &mut Loader, value| {
+            state.doubled = Some(value)
+        });
+        Self {
+            ctx,
+            state: Loader::default(),
+            response: Rc::new(Cell::new(None)),
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        let tasks = self.ctx.tasks();
+        let response = self.response.clone();
+        self.ctx
+            .view
+            .push(
+                rect()
+                    .width(50.0)
+                    .height(50.0)
+                    .finish(LOAD)
+                    .on_click(move |_: &mut Loader| {
+                        tasks.spawn(Response(response.clone()), |state: &mut Loader, value| {
+                            state.loaded = Some(value)
+                        });
+                    }),
+            );
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<Loader>, &mut Loader) {
+        (&mut self.ctx, &mut self.state)
+    }
+}
+
+fn harness() -> TestHarness<LoaderApp> {
+    TestHarness::new(Dimensions {
+        width: 100,
+        height: 100,
+    })
+}
+
+#[test]
+fn task_spawned_at_start_completes_on_poll() {
+    let mut h = harness();
+    assert_eq!(h.state().doubled, None);
+    h.advance(0.0);
+    assert_eq!(h.state().doubled, Some(42));
+}
+
+#[test]
+fn completion_runs_once_the_future_is_ready() {
+    let mut h = harness();
+    h.click_shape(LOAD).advance(100.0);
+    assert_eq!(h.state().loaded, None);
+
+    h.app().response.set(Some(7));
+    h.advance(16.0);
+    assert_eq!(h.state().loaded, Some(7));
+}
@@ -0,0 +1,143 @@
+use salt::testing::TestHarness;
+use salt::ui::{text_input, AppCtx};
+use salt::{App, AppCore, Dimensions, Modifiers};
+
+const NAME: u64 = 1;
+
+/// Advance of one character at the default 14px field font
+const CHAR_WIDTH: f32 = 14.0 * 0.6;
+
+#[derive(Default)]
+struct Form {
+    name: String,
+    edits: u32,
+}
+
+struct FormApp {
+    ctx: AppCtx<Form>,
+    state: Form,
+}
+
+impl App for FormApp {
+    type State = Form;
+
+    fn new() -> Self {
+        Self {
+            ctx: AppCtx::new(),
+            state: Form::default(),
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        self.ctx.view.push(
+            text_input()
+                .x(10.0)
+                .y(10.0)
+                .value(self.state.name.clone())
+                .placeholder("Your name")
+                .finish(NAME)
+                .on_input(|form: &mut Form, value| {
+                    form.name = value;
+                    form.edits += 1;
+                }),
+        );
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<Form>, &mut Form) {
+        (&mut self.ctx, &mut self.state)
+    }
+}
+
+fn harness() -> TestHarness<FormApp> {
+    TestHarness::new(Dimensions {
+        width: 300,
+        height: 100,
+    })
+}
+
+/// Left edge of the caret after `col` characters
+fn caret_x(col: usize) -> f32 {
+    10.0 + 6.0 + col as f32 * CHAR_WIDTH
+}
+
+fn select_all(h: &mut TestHarness<FormApp>) {
+    h.with_modifiers(Modifiers {
+        ctrl: true,
+        ..Modifiers::default()
+    })
+    .press_key("a")
+    .with_modifiers(Modifiers::default());
+}
+
+#[test]
+fn typing_into_a_focused_field_updates_the_value() {
+    let mut h = harness();
+    h.type_text("Ada");
+    assert_eq!(h.state().name, "", "nothing is focused yet");
+
+    h.click_shape(NAME).type_text("Ada");
+    assert_eq!(h.focused(), Some(NAME));
+    assert_eq!(h.state().name, "Ada");
+    assert_eq!(h.state().edits, 3);
+    assert!(h.svg().contains(">Ada</text>"));
+}
+
+#[test]
+fn caret_bounds_follow_the_caret() {
+    let mut h = harness();
+    assert_eq!(AppCore::caret_bounds(h.app()), None);
+
+    h.click_shape(NAME).type_text("Ada");
+    assert_eq!(
+        AppCore::caret_bounds(h.app()),
+        Some((caret_x(3), 16.0, caret_x(3) + 1.0, 16.0 + 14.0 * 1.2))
+    );
+
+    h.press_key("ArrowLeft").press_key("ArrowLeft");
+    assert_eq!(
+        AppCore::caret_bounds(h.app()).map(|(x, ..)| x),
+        Some(caret_x(1))
+    );
+
+    h.focus_out();
+    assert_eq!(AppCore::caret_bounds(h.app()), None);
+}
+
+#[test]
+fn editing_keys_change_the_value_at_the_caret() {
+    let mut h = harness();
+    h.click_shape(NAME).type_text("Ad");
+    h.press_key("ArrowLeft").type_text("l").press_key("End");
+    assert_eq!(h.state().name, "Ald");
+
+    h.press_key("Backspace")
+        .press_key("Home")
+        .press_key("Delete");
+    assert_eq!(h.state().name, "l");
+}
+
+#[test]
+fn clicking_places_the_caret() {
+    let mut h = harness();
+    h.state().name = "Grace".to_string();
+    h.render();
+    h.click(caret_x(2) + 1.0, 20.0).type_text("-");
+    assert_eq!(h.state().name, "Gr-ace");
+}
+
+#[test]
+fn clipboard_uses_the_selection() {
+    let mut h = harness();
+    h.click_shape(NAME).type_text("Ada");
+    assert_eq!(h.copy(), None);
+
+    select_all(&mut h);
+    assert_eq!(h.copy().as_deref(), Some("Ada"));
+    h.paste("Grace\nHopper");
+    assert_eq!(h.state().name, "Grace Hopper");
+
+    select_all(&mut h);
+    assert_eq!(h.cut().as_deref(), Some("Grace Hopper"));
+    assert_eq!(h.state().name, "");
+    assert!(h.svg().contains(">Your name</text>"));
+}
@@ -0,0 +1,134 @@
+use salt::testing::TestHarness;
+use salt::ui::{rect, AppCtx};
+use salt::{App, Dimensions};
+
+const SAVE: u64 = 1;
+const OPEN: u64 = 2;
+const PLAIN: u64 = 3;
+
+/// Default time the pointer has to rest on a shape before its tooltip shows
+const DELAY: f64 = 600.0;
+
+struct ToolbarApp {
+    ctx: AppCtx<()>,
+    state: (),
+}
+
+impl App for ToolbarApp {
+    type State = ();
+
+    fn new() -> Self {
+        Self {
+            ctx: AppCtx::new(),
+            state: (),
+        }
+    }
+
+    fn view(&mut self, _dimensions: Dimensions) {
+        self.ctx.view.push(
+            rect()
+                .width(40.0)
+                .height(40.0)
+                .finish(SAVE)
+                .tooltip("Save the drawing")
+                .on_click(|_: &mut ()| {}),
+        );
+        self.ctx.view.push(
+            rect()
+                .x(50.0)
+                .width(40.0)
+                .height(40.0)
+                .finish(OPEN)
+                .tooltip("Open a drawing"),
+        );
+        self.ctx
+            .view
+            .push(rect().x(100.0).width(40.0).height(40.0).finish(PLAIN));
+    }
+
+    fn state(&mut self) -> (&mut AppCtx<()>, &mut ()) {
+        (&mut self.ctx, &mut self.state)
+    }
+}
+
+fn harness() -> TestHarness<ToolbarApp> {
+    TestHarness::new(Dimensions {
+        width: 300,
+        height: 200,
+    })
+}
+
+#[test]
+fn tooltip_shows_after_the_delay() {
+    let mut h = harness();
+    h.hover_shape(SAVE).advance(DELAY - 1.0);
+    assert_eq!(h.tooltip(), None);
+    assert!(!h.svg().contains("salt-tooltip"));
+
+    h.advance(1.0);
+    assert_eq!(h.tooltip(), Some("Save the drawing"));
+}
+
+#[test]
+fn tooltip_is_drawn_in_the_svg() {
+    let mut h = harness();
+    h.hover_shape(SAVE).advance(DELAY);
+    let svg = h.svg();
+    let overlay = &svg[svg.find("salt-tooltip").expect("tooltip overlay")..];
+    assert!(overlay.contains(">Save the drawing</text>"));
+}
+
+#[test]
+fn moving_within_the_shape_keeps_the_delay() {
+    let mut h = harness();
+    h.hover(10.0, 10.0).advance(DELAY / 2.0);
+    h.hover(30.0, 30.0).advance(DELAY / 2.0 - 16.0);
+    assert_eq!(h.tooltip(), Some("Save the drawing"));
+}
+
+#[test]
+fn leaving_the_shape_hides_the_tooltip() {
+    let mut h = harness();
+    h.hover_shape(SAVE).advance(DELAY);
+    assert!(h.tooltip().is_some());
+
+    h.hover_shape(PLAIN);
+    assert_eq!(h.tooltip(), None);
+    assert!(!h.svg().contains("salt-tooltip"));
+    h.advance(DELAY);
+    assert_eq!(h.tooltip(), None, "the plain shape has no tooltip");
+}
+
+#[test]
+fn entering_another_shape_restarts_the_delay() {
+    let mut h = harness();
+    h.hover_shape(SAVE).advance(DELAY);
+    h.hover_shape(OPEN);
+    assert_eq!(h.tooltip(), None);
+
+    h.advance(DELAY);
+    assert_eq!(h.tooltip(), Some("Open a drawing"));
+}
+
+#[test]
+fn pressing_hides_the_tooltip_until_the_pointer_leaves() {
+    let mut h = harness();
+    h.hover_shape(SAVE).advance(DELAY);
+    assert!(h.tooltip().is_some());
+
+    h.click_shape(SAVE);
+    assert_eq!(h.tooltip(), None);
+    h.advance(DELAY * 2.0);
+    assert_eq!(h.tooltip(), None);
+
+    h.hover_shape(OPEN).hover_shape(SAVE).advance(DELAY);
+    assert_eq!(h.tooltip(), Some("Save the drawing"));
+}
+
+#[test]
+fn pressing_before_the_delay_cancels_the_tooltip() {
+    let mut h = harness();
+    h.hover_shape(SAVE).advance(DELAY / 2.0);
+    h.pointer_down(20.0, 20.0).advance(DELAY);
+    assert_eq!(h.tooltip(), None);
+}
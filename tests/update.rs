@@ -0,0 +1,60 @@
+use salt::ui::{DiffLine, Store, Update};
+
+#[derive(Default)]
+struct Form {
+    count: u32,
+    label: String,
+    open: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Msg {
+    Set(u32, &'static str),
+    Noop,
+}
+
+impl Update for Form {
+    type Msg = Msg;
+
+    fn update(&mut self, msg: Msg) {
+        if let Msg::Set(count, label) = msg {
+            self.count = count;
+            self.label = label.to_string();
+        }
+    }
+}
+
+fn describe(form: &Form) -> String {
+    format!(
+        "count: {}\nlabel: {:?}\nopen: {}",
+        form.count, form.label, form.open
+    )
+}
+
+#[test]
+fn diff_lists_removed_lines_before_added_ones() {
+    let mut store = Store::new(Form::default()).trace_with(describe);
+    store.dispatch(Msg::Set(1, "a"));
+    store.dispatch(Msg::Set(2, "b"));
+    assert_eq!(
+        store.log()[1].diff,
+        [
+            DiffLine::Removed("count: 1".to_string()),
+            DiffLine::Removed("label: \"a\"".to_string()),
+            DiffLine::Added("count: 2".to_string()),
+            DiffLine::Added("label: \"b\"".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn unchanged_state_has_empty_diff() {
+    let mut store = Store::new(Form::default()).trace_with(describe);
+    store.dispatch(Msg::Noop);
+    assert!(store.log()[0].diff.is_empty());
+
+    let mut untraced = Store::new(Form::default());
+    untraced.dispatch(Msg::Set(1, "a"));
+    assert!(untraced.log()[0].diff.is_empty());
+    assert_eq!(untraced.messages().count(), 1);
+}